@@ -0,0 +1,178 @@
+// archive.rs
+//! Retention of settled escrows.
+//!
+//! When an escrow reaches a terminal status (`Completed`, `Cancelled`, `Resolved`
+//! or `Expired`) its settlement time is recorded. Once the retention period has
+//! passed, anyone may call `archive_escrow` to replace the escrow, its milestones
+//! and its fee override with a `SettlementReceipt`. Archived escrows stay in their
+//! parties' indexes and in the status counts under their final status.
+#[cfg(test)]
+use crate::__VaultixEscrow_fn_set_registry;
+use crate::events::{publish, publish_for, EscrowArchived, RetentionPeriodUpdated};
+use crate::storage::has_legacy_escrow;
+use crate::{
+    get_admin, get_persistent, get_stored_config, governance::submit_action, load_escrow,
+    multisig::ensure_multisig_disabled, set_persistent, set_stored_config, AdminAction, DataKey,
+    Error, EscrowStatus, Resolution, VaultixEscrow, VaultixEscrowClient, MAX_MILESTONES_PER_CALL,
+};
+use soroban_sdk::{contractimpl, contracttype, Address, Env};
+
+/// Default time (in seconds) a settled escrow is kept before it can be archived.
+pub(crate) const DEFAULT_RETENTION_PERIOD: u64 = 7_776_000;
+
+/// What remains of an escrow after archival.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SettlementReceipt {
+    pub depositor: Address,
+    pub recipient: Address,
+    pub token_address: Address,
+    pub total_amount: i128,
+    pub total_released: i128,
+    pub status: EscrowStatus,
+    pub resolution: Resolution,
+    pub settled_at: u64,
+    pub archived_at: u64,
+}
+
+#[contractimpl]
+impl VaultixEscrow {
+    /// Set how long settled escrows are kept before they can be archived.
+    /// Only admin can call. The new period is queued and applies to archive calls
+    /// made after the queued change is executed.
+    pub fn set_retention_period(env: Env, period: u64) -> Result<(), Error> {
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+        ensure_multisig_disabled(&env)?;
+
+        submit_action(&env, AdminAction::SetRetentionPeriod(period), admin)
+    }
+
+    pub fn get_retention_period(env: Env) -> u64 {
+        get_retention_period(&env)
+    }
+
+    /// Compact a settled escrow into its settlement receipt.
+    /// Anyone can call this once the retention period has passed since settlement.
+    /// Each call removes up to `MAX_MILESTONES_PER_CALL` milestones and returns
+    /// `None` while more remain; the call removing the last ones replaces the
+    /// escrow with its receipt.
+    pub fn archive_escrow(env: Env, escrow_id: u64) -> Result<Option<SettlementReceipt>, Error> {
+        let escrow = load_escrow(&env, escrow_id)?;
+        if !is_terminal(escrow.status) {
+            return Err(Error::InvalidEscrowStatus);
+        }
+        // Escrows settled under version 1 are timestamped by `migrate`
+        let settled_at: u64 = get_persistent(&env, &DataKey::SettledAt(escrow_id))
+            .ok_or(Error::InvalidEscrowStatus)?;
+        let now = env.ledger().timestamp();
+        if now < settled_at.saturating_add(get_retention_period(&env)) {
+            return Err(Error::TimelockNotReady);
+        }
+
+        // The escrow record stays intact until the receipt replaces it
+        let persistent = env.storage().persistent();
+        let cursor_key = DataKey::ArchivedMilestones(escrow_id);
+        let start = get_archived_milestones(&env, escrow_id);
+        let end = start
+            .saturating_add(MAX_MILESTONES_PER_CALL)
+            .min(escrow.milestone_count);
+        for index in start..end {
+            persistent.remove(&DataKey::Milestone(escrow_id, index));
+        }
+        if end < escrow.milestone_count {
+            set_persistent(&env, &cursor_key, &end);
+            return Ok(None);
+        }
+
+        let receipt = SettlementReceipt {
+            depositor: escrow.depositor,
+            recipient: escrow.recipient,
+            token_address: escrow.token_address,
+            total_amount: escrow.total_amount,
+            total_released: escrow.total_released,
+            status: escrow.status,
+            resolution: escrow.resolution,
+            settled_at,
+            archived_at: now,
+        };
+
+        persistent.remove(&cursor_key);
+        persistent.remove(&DataKey::EscrowFee(escrow_id));
+        persistent.remove(&DataKey::SettledAt(escrow_id));
+        persistent.remove(&DataKey::Escrow(escrow_id));
+        set_persistent(&env, &DataKey::Receipt(escrow_id), &receipt);
+
+        publish_for(&env, escrow_id, EscrowArchived { settled_at });
+
+        Ok(Some(receipt))
+    }
+
+    pub fn get_receipt(env: Env, escrow_id: u64) -> Result<SettlementReceipt, Error> {
+        get_persistent(&env, &DataKey::Receipt(escrow_id)).ok_or(Error::EscrowNotFound)
+    }
+}
+
+fn get_retention_period(env: &Env) -> u64 {
+    get_stored_config(env)
+        .map(|config| config.retention_period)
+        .unwrap_or(DEFAULT_RETENTION_PERIOD)
+}
+
+pub(crate) fn apply_retention_period(env: &Env, period: u64) -> Result<(), Error> {
+    let mut config = get_stored_config(env).ok_or(Error::AdminNotInitialized)?;
+    let old_period = config.retention_period;
+    config.retention_period = period;
+    set_stored_config(env, &config);
+
+    publish(
+        env,
+        RetentionPeriodUpdated {
+            old_period,
+            new_period: period,
+        },
+    );
+
+    Ok(())
+}
+
+/// Number of leading milestones of `escrow_id` already removed by `archive_escrow`.
+pub(crate) fn get_archived_milestones(env: &Env, escrow_id: u64) -> u32 {
+    get_persistent(env, &DataKey::ArchivedMilestones(escrow_id)).unwrap_or(0)
+}
+
+pub(crate) fn is_terminal(status: EscrowStatus) -> bool {
+    matches!(
+        status,
+        EscrowStatus::Completed
+            | EscrowStatus::Cancelled
+            | EscrowStatus::Resolved
+            | EscrowStatus::Expired
+    )
+}
+
+/// Records when an escrow reached a terminal status.
+pub(crate) fn record_settlement(env: &Env, escrow_id: u64) {
+    set_persistent(
+        env,
+        &DataKey::SettledAt(escrow_id),
+        &env.ledger().timestamp(),
+    );
+}
+
+/// Whether `escrow_id` is taken by a live, an archived or a not yet migrated escrow.
+pub(crate) fn escrow_exists(env: &Env, escrow_id: u64) -> bool {
+    let persistent = env.storage().persistent();
+    persistent.has(&DataKey::Escrow(escrow_id))
+        || persistent.has(&DataKey::Receipt(escrow_id))
+        || has_legacy_escrow(env, escrow_id)
+}
+
+/// Status of a live escrow, or the final status of an archived one.
+pub(crate) fn escrow_status(env: &Env, escrow_id: u64) -> Option<EscrowStatus> {
+    match load_escrow(env, escrow_id) {
+        Ok(escrow) => Some(escrow.status),
+        Err(_) => get_persistent::<_, SettlementReceipt>(env, &DataKey::Receipt(escrow_id))
+            .map(|receipt| receipt.status),
+    }
+}
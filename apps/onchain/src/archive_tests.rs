@@ -0,0 +1,177 @@
+use crate::test_fixtures::{create_funded, execute_queued, milestones, setup};
+use crate::{
+    DataKey, Error, EscrowStatus, EscrowV1, Resolution, SettlementReceipt, StatusCounts,
+    VaultixEscrowClient, CONTRACT_VERSION, MAX_MILESTONES_PER_CALL,
+};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    vec, Address, Env,
+};
+
+/// Retention period used by these tests, in seconds.
+const RETENTION: u64 = 86_400;
+
+/// Helper function to set up the contract without fees and with the test retention period
+fn setup_with_retention(env: &Env) -> (VaultixEscrowClient<'_>, Address) {
+    let (client, token_address) = setup(env, Some(0));
+    client.set_retention_period(&RETENTION);
+    execute_queued(env, &client);
+    (client, token_address)
+}
+
+fn advance(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|ledger| ledger.timestamp += seconds);
+}
+
+#[test]
+fn test_settled_escrow_is_archived_after_retention() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_address) = setup_with_retention(&env);
+    env.ledger().with_mut(|ledger| ledger.timestamp = 1_000);
+
+    let (depositor, recipient) = create_funded(&env, &client, 1, &token_address, &[1_000, 2_000]);
+    let result = client.try_archive_escrow(&1);
+    assert_eq!(result, Err(Ok(Error::InvalidEscrowStatus)));
+
+    client.release_milestones(&1, &vec![&env, 0u32, 1u32]);
+    client.complete_escrow(&1);
+
+    // Retention runs from settlement
+    advance(&env, RETENTION - 1);
+    let result = client.try_archive_escrow(&1);
+    assert_eq!(result, Err(Ok(Error::TimelockNotReady)));
+
+    advance(&env, 1);
+    let receipt = client.archive_escrow(&1).unwrap();
+    assert_eq!(
+        receipt,
+        SettlementReceipt {
+            depositor: depositor.clone(),
+            recipient,
+            token_address,
+            total_amount: 3_000,
+            total_released: 3_000,
+            status: EscrowStatus::Completed,
+            resolution: Resolution::None,
+            settled_at: 1_000,
+            archived_at: 1_000 + RETENTION,
+        }
+    );
+    assert_eq!(client.get_receipt(&1), receipt);
+
+    // The escrow and its milestones are gone, the receipt keeps its ID taken
+    assert_eq!(client.try_get_state(&1), Err(Ok(Error::EscrowNotFound)));
+    let result = client.try_get_milestone(&1, &0);
+    assert_eq!(result, Err(Ok(Error::EscrowNotFound)));
+    let result = client.try_archive_escrow(&1);
+    assert_eq!(result, Err(Ok(Error::EscrowNotFound)));
+    let result = client.try_create_escrow(
+        &1,
+        &depositor,
+        &Address::generate(&env),
+        &receipt.token_address,
+        &milestones(&env, &[1_000]),
+        &1_706_400_000u64,
+    );
+    assert_eq!(result, Err(Ok(Error::EscrowAlreadyExists)));
+
+    // Indexes and counts still report the final status
+    let page = client.get_escrows_by_depositor(&depositor, &0, &10, &Some(EscrowStatus::Completed));
+    assert_eq!(page.escrow_ids, vec![&env, 1u64]);
+    assert_eq!(
+        client.get_status_counts(),
+        StatusCounts {
+            completed: 1,
+            ..StatusCounts::default()
+        }
+    );
+}
+
+#[test]
+fn test_large_escrow_is_archived_over_several_calls() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_address) = setup_with_retention(&env);
+
+    let chunk = milestones(&env, &[100; MAX_MILESTONES_PER_CALL as usize]);
+    client.create_escrow(
+        &1,
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &token_address,
+        &chunk,
+        &1_706_400_000u64,
+    );
+    client.add_milestones(&1, &chunk);
+    client.add_milestones(&1, &milestones(&env, &[100]));
+    client.cancel_escrow(&1);
+    advance(&env, RETENTION);
+
+    // Each call removes one page of milestones, leaving the escrow record as is
+    let depositor = client.get_escrow(&1).depositor;
+    assert_eq!(client.archive_escrow(&1), None);
+    assert_eq!(
+        client.get_escrow(&1).milestone_count,
+        2 * MAX_MILESTONES_PER_CALL + 1
+    );
+    let result = client.try_get_milestone(&1, &0);
+    assert_eq!(result, Err(Ok(Error::MilestoneNotFound)));
+    assert_eq!(
+        client.bump_escrow(&1, &0),
+        Some(2 * MAX_MILESTONES_PER_CALL)
+    );
+    assert_eq!(client.archive_escrow(&1), None);
+    assert_eq!(
+        client.get_escrow(&1).milestone_count,
+        2 * MAX_MILESTONES_PER_CALL + 1
+    );
+    let page = client.get_escrows_by_depositor(&depositor, &0, &10, &None);
+    assert_eq!(page.escrow_ids.len(), 1);
+
+    let receipt = client.archive_escrow(&1).unwrap();
+    assert_eq!(
+        receipt.total_amount,
+        100 * (2 * MAX_MILESTONES_PER_CALL as i128 + 1)
+    );
+    assert_eq!(client.try_get_state(&1), Err(Ok(Error::EscrowNotFound)));
+}
+
+#[test]
+fn test_migrate_timestamps_previously_settled_escrows() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_address) = setup_with_retention(&env);
+
+    // Version 1 did not record settlement times
+    env.as_contract(&client.address, || {
+        env.storage().instance().remove(&DataKey::Version);
+        let legacy = EscrowV1 {
+            depositor: Address::generate(&env),
+            recipient: Address::generate(&env),
+            token_address: token_address.clone(),
+            total_amount: 1_000,
+            total_released: 0,
+            milestones: milestones(&env, &[1_000]),
+            status: EscrowStatus::Cancelled,
+            deadline: 1_706_400_000,
+            resolution: Resolution::None,
+        };
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("escrow"), 1u64), &legacy);
+    });
+    advance(&env, RETENTION);
+
+    assert_eq!(client.migrate(&1, &vec![&env, 1u64], &vec![&env]), 1);
+    assert_eq!(client.get_version(), CONTRACT_VERSION);
+
+    let result = client.try_archive_escrow(&1);
+    assert_eq!(result, Err(Ok(Error::TimelockNotReady)));
+    advance(&env, RETENTION);
+    assert_eq!(
+        client.archive_escrow(&1).unwrap().status,
+        EscrowStatus::Cancelled
+    );
+}
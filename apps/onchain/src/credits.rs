@@ -0,0 +1,126 @@
+// credits.rs
+//! Pull payments.
+//!
+//! In credit mode escrow payouts are not transferred to the payee. They are
+//! recorded as a claimable balance per address and token, which the holder
+//! withdraws with `withdraw`. A payee that cannot receive the token (e.g. a
+//! missing trustline or a frozen account) then only blocks its own withdrawal
+//! rather than the settlement. Credited funds stay in the token's liabilities
+//! until withdrawn, but count as released rather than locked in the stats.
+#[cfg(test)]
+use crate::__VaultixEscrow_fn_set_registry;
+use crate::events::{publish, publish_for, CreditModeUpdated, CreditWithdrawn, PayoutCredited};
+use crate::{
+    get_admin, get_persistent, get_stored_config,
+    governance::submit_action,
+    multisig::ensure_multisig_disabled,
+    release_liabilities, safe_transfer, set_persistent, set_stored_config,
+    stats::{record_credit, record_credit_withdrawn},
+    AdminAction, DataKey, Error, VaultixEscrow, VaultixEscrowClient,
+};
+use soroban_sdk::{contractimpl, token, Address, Env};
+
+#[contractimpl]
+impl VaultixEscrow {
+    /// Switch escrow payouts between direct transfers and claimable credits.
+    /// Only admin can call. Credits recorded earlier stay withdrawable either way.
+    /// The switch is queued, so payouts keep their current mode until it executes.
+    pub fn set_credit_mode(env: Env, enabled: bool) -> Result<(), Error> {
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+        ensure_multisig_disabled(&env)?;
+
+        submit_action(&env, AdminAction::SetCreditMode(enabled), admin)
+    }
+
+    pub fn is_credit_mode(env: Env) -> bool {
+        is_credit_mode(&env)
+    }
+
+    /// Returns the balance of `token_address` that `addr` can withdraw.
+    pub fn get_credit(env: Env, addr: Address, token_address: Address) -> i128 {
+        get_credit(&env, &addr, &token_address)
+    }
+
+    /// Transfer the full credited balance of `token_address` to `addr`.
+    /// Only `addr` can call. Returns the amount withdrawn.
+    pub fn withdraw(env: Env, addr: Address, token_address: Address) -> Result<i128, Error> {
+        addr.require_auth();
+
+        let amount = get_credit(&env, &addr, &token_address);
+        if amount <= 0 {
+            return Err(Error::NoCreditToWithdraw);
+        }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Credit(addr.clone(), token_address.clone()));
+
+        let token_client = token::Client::new(&env, &token_address);
+        safe_transfer(
+            &token_client,
+            &env.current_contract_address(),
+            &addr,
+            amount,
+        )?;
+        release_liabilities(&env, &token_address, amount);
+        record_credit_withdrawn(&env, &token_address, amount)?;
+
+        publish_for(
+            &env,
+            addr,
+            CreditWithdrawn {
+                token_address,
+                amount,
+            },
+        );
+
+        Ok(amount)
+    }
+}
+
+pub(crate) fn apply_credit_mode(env: &Env, enabled: bool) -> Result<(), Error> {
+    let mut config = get_stored_config(env).ok_or(Error::AdminNotInitialized)?;
+    config.credit_mode = enabled;
+    set_stored_config(env, &config);
+
+    publish(env, CreditModeUpdated { enabled });
+
+    Ok(())
+}
+
+pub(crate) fn is_credit_mode(env: &Env) -> bool {
+    get_stored_config(env).is_some_and(|config| config.credit_mode)
+}
+
+fn get_credit(env: &Env, addr: &Address, token_address: &Address) -> i128 {
+    get_persistent(env, &DataKey::Credit(addr.clone(), token_address.clone())).unwrap_or(0)
+}
+
+/// Adds `amount` to the balance `addr` can withdraw.
+pub(crate) fn add_credit(
+    env: &Env,
+    addr: &Address,
+    token_address: &Address,
+    amount: i128,
+) -> Result<(), Error> {
+    let balance = get_credit(env, addr, token_address)
+        .checked_add(amount)
+        .ok_or(Error::InvalidMilestoneAmount)?;
+    set_persistent(
+        env,
+        &DataKey::Credit(addr.clone(), token_address.clone()),
+        &balance,
+    );
+    record_credit(env, token_address, amount)?;
+
+    publish_for(
+        env,
+        addr.clone(),
+        PayoutCredited {
+            token_address: token_address.clone(),
+            amount,
+        },
+    );
+
+    Ok(())
+}
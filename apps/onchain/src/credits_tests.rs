@@ -0,0 +1,79 @@
+use crate::test_fixtures::{create_funded, execute_queued, setup};
+use crate::{Error, VaultixEscrowClient};
+use soroban_sdk::{testutils::Address as _, token, vec, Address, Env};
+
+/// Helper function to set up the contract in credit mode with a 1% platform fee
+fn setup_credit_mode(env: &Env) -> (VaultixEscrowClient<'_>, Address) {
+    let (client, token_address) = setup(env, Some(100));
+    client.set_credit_mode(&true);
+    execute_queued(env, &client);
+    (client, token_address)
+}
+
+#[test]
+fn test_payouts_are_credited_and_withdrawn() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_address) = setup_credit_mode(&env);
+    let token_client = token::Client::new(&env, &token_address);
+
+    let (_, recipient) = create_funded(&env, &client, 1, &token_address, &[4_000, 6_000]);
+    let (depositor, _) = create_funded(&env, &client, 2, &token_address, &[4_000, 6_000]);
+    client.release_milestone(&1, &0);
+    client.cancel_escrow(&2);
+
+    // Nothing leaves the contract until withdrawn, 1% is retained each time
+    assert_eq!(token_client.balance(&recipient), 0);
+    assert_eq!(token_client.balance(&depositor), 0);
+    assert_eq!(client.get_credit(&recipient, &token_address), 3_960);
+    assert_eq!(client.get_credit(&depositor, &token_address), 9_900);
+    assert_eq!(
+        client.get_liabilities(&token_address),
+        6_000 + 3_960 + 9_900
+    );
+    // Credited payouts count as released, not as value still locked
+    let stats = client.get_stats(&token_address);
+    assert_eq!(stats.total_released, 3_960);
+    assert_eq!(stats.total_credited, 3_960 + 9_900);
+    assert_eq!(stats.total_value_locked, 6_000);
+
+    assert_eq!(client.withdraw(&recipient, &token_address), 3_960);
+    assert_eq!(token_client.balance(&recipient), 3_960);
+    assert_eq!(client.get_credit(&recipient, &token_address), 0);
+    assert_eq!(client.get_liabilities(&token_address), 6_000 + 9_900);
+    assert_eq!(client.get_stats(&token_address).total_credited, 9_900);
+    assert_eq!(client.get_stats(&token_address).total_value_locked, 6_000);
+
+    let result = client.try_withdraw(&recipient, &token_address);
+    assert_eq!(result, Err(Ok(Error::NoCreditToWithdraw)));
+
+    // Credits stay withdrawable after switching back to transfers
+    client.set_credit_mode(&false);
+    execute_queued(&env, &client);
+    assert_eq!(client.withdraw(&depositor, &token_address), 9_900);
+    assert_eq!(token_client.balance(&depositor), 9_900);
+    assert_eq!(client.get_liabilities(&token_address), 6_000);
+}
+
+#[test]
+fn test_failing_recipient_transfer_does_not_block_settlement() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_address) = setup_credit_mode(&env);
+    let token_client = token::Client::new(&env, &token_address);
+
+    // A full balance makes every transfer to the recipient overflow
+    let (_, recipient) = create_funded(&env, &client, 1, &token_address, &[4_000, 6_000]);
+    token::StellarAssetClient::new(&env, &token_address).mint(&recipient, &i128::MAX);
+
+    // Settlement only records the credit; the host aborts on the token's overflow,
+    // so the failing withdrawal itself cannot be exercised here
+    client.release_milestones(&1, &vec![&env, 0u32, 1u32]);
+    client.complete_escrow(&1);
+    assert_eq!(client.get_credit(&recipient, &token_address), 9_900);
+
+    // Once the recipient can receive again the credit is still there
+    token_client.transfer(&recipient, &Address::generate(&env), &i128::MAX);
+    assert_eq!(client.withdraw(&recipient, &token_address), 9_900);
+    assert_eq!(token_client.balance(&recipient), 9_900);
+}
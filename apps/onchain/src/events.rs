@@ -0,0 +1,574 @@
+// events.rs
+//! Contract events and their schema.
+//!
+//! Every event is published with the topics
+//! `("Vaultix", <event name>, EVENT_SCHEMA_VERSION)`, followed by a subject topic
+//! for events about one escrow, token, address, role, queued change or multisig
+//! proposal. The event name is the name of its struct below, and the data is that
+//! struct, encoded as a map keyed by field name.
+//!
+//! This layout is a stable interface for indexers. Within a schema version,
+//! events are never renamed or removed and existing fields keep their name and
+//! type; new events and fields may be added, so decode by field name and skip
+//! unknown events. Any other change bumps `EVENT_SCHEMA_VERSION`.
+//!
+//! Subject topics:
+//! - escrow ID (`u64`): escrow lifecycle events from `EscrowCreated` to `EscrowFeeUpdated`
+//! - token address: `FeesWithdrawn`, `ExcessSwept`, `TokenAllowed`, `TokenRemoved`
+//! - token address, or void for the global fee: `FeeChangeScheduled`, `FeeChangeApplied`
+//! - account address: `FeeDiscountAdded`, `FeeDiscountRemoved`, `PayoutCredited`,
+//!   `CreditWithdrawn`
+//! - role name (`Symbol`): role events
+//! - change ID (`u64`): `ChangeQueued`, `ChangeExecuted`, `ChangeCancelled`
+//! - proposal ID (`u64`): `ActionProposed`, `ActionApproved`, `ActionExecuted`
+//!
+//! Builds with the `debug-events` feature also publish tracing events from
+//! `cancel_escrow`. They are for tests only and not part of the schema.
+#[cfg(feature = "debug-events")]
+use crate::EscrowStatus;
+use crate::{AdminAction, FeePayer, PausableOperation, Role};
+use soroban_sdk::{contracttype, Address, BytesN, Env, IntoVal, Symbol, Val, Vec};
+
+/// Version of the event layout, published as the third topic of every event.
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
+
+/// An event payload published under its own name.
+pub(crate) trait ContractEvent: IntoVal<Env, Val> {
+    const NAME: &'static str;
+}
+
+macro_rules! contract_events {
+    ($($name:ident),* $(,)?) => {
+        $(
+            impl ContractEvent for $name {
+                const NAME: &'static str = stringify!($name);
+            }
+        )*
+    };
+}
+
+/// Publishes a contract-wide event.
+pub(crate) fn publish<E: ContractEvent>(env: &Env, event: E) {
+    env.events().publish(
+        (
+            Symbol::new(env, "Vaultix"),
+            Symbol::new(env, E::NAME),
+            EVENT_SCHEMA_VERSION,
+        ),
+        event,
+    );
+}
+
+/// Publishes an event about `subject`, which indexers can filter on.
+pub(crate) fn publish_for<S, E>(env: &Env, subject: S, event: E)
+where
+    S: IntoVal<Env, Val>,
+    E: ContractEvent,
+{
+    env.events().publish(
+        (
+            Symbol::new(env, "Vaultix"),
+            Symbol::new(env, E::NAME),
+            EVENT_SCHEMA_VERSION,
+            subject,
+        ),
+        event,
+    );
+}
+
+// Escrow lifecycle, keyed by escrow ID
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EscrowCreated {
+    pub depositor: Address,
+    pub recipient: Address,
+    pub token_address: Address,
+    pub total_amount: i128,
+    pub deadline: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EscrowFunded {
+    pub amount: i128,
+    pub fee_reserve: i128, // Pulled on top of `amount` in depositor-pays mode
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeePayerUpdated {
+    pub fee_payer: FeePayer,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MilestoneReleased {
+    pub milestone_index: u32,
+    pub payout: i128,
+    pub fee: i128,
+}
+
+/// Milestones appended by `add_milestones`, with the escrow's new total.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MilestonesAdded {
+    pub count: u32,
+    pub total_amount: i128,
+}
+
+/// Summary of a `release_milestones` batch, after one `MilestoneReleased` per milestone.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MilestonesReleased {
+    pub count: u32,
+    pub total_payout: i128,
+    pub total_fee: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DisputeRaised {
+    pub raised_by: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DisputeResolved {
+    pub winner: Address,
+    pub amount_to_winner: i128,
+    pub amount_to_other: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EscrowCancelled {
+    pub depositor: Address,
+    pub refund: i128, // Zero when cancelled before funding
+    pub fee: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EscrowCompleted {
+    pub total_released: i128,
+    pub reserve_refunded: i128, // Unused fee reserve returned to the depositor
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RefundExpired {
+    pub depositor: Address,
+    pub refund: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EmergencyWithdrawal {
+    pub depositor: Address,
+    pub refund: i128,
+}
+
+/// The escrow was replaced by its settlement receipt, see `get_receipt`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EscrowArchived {
+    pub settled_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EscrowFeeUpdated {
+    pub old_fee_bps: i128,
+    pub new_fee_bps: i128,
+}
+
+// Fees and treasury
+
+/// Platform fee set at initialisation.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeeUpdated {
+    pub old_fee_bps: i128,
+    pub new_fee_bps: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeeChangeDelayUpdated {
+    pub old_delay: u64,
+    pub new_delay: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeeChangeScheduled {
+    pub fee_bps: i128,
+    pub eta: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeeChangeApplied {
+    pub old_fee_bps: i128,
+    pub new_fee_bps: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeeDiscountAdded {
+    pub discount_bps: i128,
+    pub valid_until: Option<u64>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeeDiscountRemoved {
+    pub removed_by: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeesWithdrawn {
+    pub to: Address,
+    pub amount: i128,
+    pub remaining: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExcessSwept {
+    pub to: Address,
+    pub amount: i128,
+}
+
+/// A payout recorded as a claimable balance in credit mode.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PayoutCredited {
+    pub token_address: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CreditWithdrawn {
+    pub token_address: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CreditModeUpdated {
+    pub enabled: bool,
+}
+
+// Tokens, pausing and retention
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TokenAllowed {
+    pub min_amount: i128,
+    pub max_amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TokenRemoved {
+    pub removed_by: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PausedStateChanged {
+    pub paused: bool,
+    pub actor: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OperationPaused {
+    pub operation: PausableOperation,
+    pub paused: bool,
+    pub actor: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TokenPaused {
+    pub token_address: Address,
+    pub paused: bool,
+    pub actor: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EmergencyExitDelayUpdated {
+    pub delay: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RetentionPeriodUpdated {
+    pub old_period: u64,
+    pub new_period: u64,
+}
+
+// Roles, keyed by role name
+
+/// First holder of a role, set at initialisation.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RoleAssigned {
+    pub holder: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RoleUpdated {
+    pub previous: Address,
+    pub holder: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RoleTransferProposed {
+    pub current: Address,
+    pub proposed: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RoleTransferCancelled {
+    pub proposed: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RoleGranted {
+    pub account: Address,
+    pub actor: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RoleRevoked {
+    pub account: Address,
+    pub actor: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RoleAdminChanged {
+    pub previous_admin_role: Role,
+    pub new_admin_role: Role,
+}
+
+// Screening
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScreeningContractSet {
+    pub screener: Address,
+}
+
+/// Screening fell back to the local blocklist.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScreeningContractCleared {
+    pub cleared_by: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AddressBlockUpdated {
+    pub addr: Address,
+    pub blocked: bool,
+}
+
+// Governance queue, keyed by change ID, and multisig, keyed by proposal ID
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GovernanceDelayUpdated {
+    pub old_delay: u64,
+    pub new_delay: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChangeQueued {
+    pub action: AdminAction,
+    pub queued_by: Address,
+    pub eta: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChangeExecuted {
+    pub queued_by: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChangeCancelled {
+    pub cancelled_by: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultisigUpdated {
+    pub signers: Vec<Address>,
+    pub threshold: u32,
+    pub proposal_ttl: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ActionProposed {
+    pub proposer: Address,
+    pub action: AdminAction,
+    pub expires_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ActionApproved {
+    pub signer: Address,
+    pub approvals: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ActionExecuted {
+    pub action: AdminAction,
+}
+
+// Upgrades
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UpgradeProposed {
+    pub wasm_hash: BytesN<32>,
+    pub eta: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UpgradeCancelled {
+    pub wasm_hash: BytesN<32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContractUpgraded {
+    pub wasm_hash: BytesN<32>,
+    pub version: u32, // Storage layout version of the code being replaced
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContractMigrated {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub migrated: u32, // Escrows rewritten by this call
+}
+
+contract_events!(
+    EscrowCreated,
+    EscrowFunded,
+    MilestonesAdded,
+    FeePayerUpdated,
+    MilestoneReleased,
+    MilestonesReleased,
+    DisputeRaised,
+    DisputeResolved,
+    EscrowCancelled,
+    EscrowCompleted,
+    RefundExpired,
+    EmergencyWithdrawal,
+    EscrowArchived,
+    EscrowFeeUpdated,
+    FeeUpdated,
+    FeeChangeDelayUpdated,
+    FeeChangeScheduled,
+    FeeChangeApplied,
+    FeeDiscountAdded,
+    FeeDiscountRemoved,
+    FeesWithdrawn,
+    ExcessSwept,
+    PayoutCredited,
+    CreditWithdrawn,
+    CreditModeUpdated,
+    TokenAllowed,
+    TokenRemoved,
+    PausedStateChanged,
+    OperationPaused,
+    TokenPaused,
+    EmergencyExitDelayUpdated,
+    RetentionPeriodUpdated,
+    RoleAssigned,
+    RoleUpdated,
+    RoleTransferProposed,
+    RoleTransferCancelled,
+    RoleGranted,
+    RoleRevoked,
+    RoleAdminChanged,
+    ScreeningContractSet,
+    ScreeningContractCleared,
+    AddressBlockUpdated,
+    GovernanceDelayUpdated,
+    ChangeQueued,
+    ChangeExecuted,
+    ChangeCancelled,
+    MultisigUpdated,
+    ActionProposed,
+    ActionApproved,
+    ActionExecuted,
+    UpgradeProposed,
+    UpgradeCancelled,
+    ContractUpgraded,
+    ContractMigrated,
+);
+
+// Tracing for `cancel_escrow`, only built with the `debug-events` feature
+
+#[cfg(feature = "debug-events")]
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CancelStart {
+    pub total_amount: i128,
+    pub total_released: i128,
+    pub status: EscrowStatus,
+}
+
+#[cfg(feature = "debug-events")]
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeeResolved {
+    pub fee_bps: i128,
+    pub fee: i128,
+}
+
+#[cfg(feature = "debug-events")]
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeeTransferAttempt {
+    pub fee: i128,
+}
+
+#[cfg(feature = "debug-events")]
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RefundAmountComputed {
+    pub refund: i128,
+}
+
+#[cfg(feature = "debug-events")]
+contract_events!(
+    CancelStart,
+    FeeResolved,
+    FeeTransferAttempt,
+    RefundAmountComputed
+);
@@ -1,4 +1,8 @@
-use crate::{Error, Milestone, MilestoneStatus, VaultixEscrow, VaultixEscrowClient};
+use crate::test_fixtures::{create_token_contract, execute_queued, init_contract};
+use crate::{
+    Error, FeePayer, Milestone, MilestoneStatus, Role, SettlementQuote, VaultixEscrow,
+    VaultixEscrowClient, MIN_GOVERNANCE_DELAY,
+};
 use soroban_sdk::symbol_short;
 /// Comprehensive tests for the Configurable Fee Model feature (#93)
 /// Tests cover:
@@ -10,26 +14,9 @@ use soroban_sdk::symbol_short;
 /// - Fee precedence: escrow > token > global
 use soroban_sdk::{
     testutils::{Address as _, Ledger},
-    token, vec, Address, Env,
+    vec, Address, Env,
 };
 
-/// Helper function to create and initialize a test token
-fn create_test_token<'a>(env: &Env, admin: &Address) -> (token::StellarAssetClient<'a>, Address) {
-    let token_address = env.register_stellar_asset_contract(admin.clone());
-    let token_admin_client = token::StellarAssetClient::new(env, &token_address);
-    (token_admin_client, token_address)
-}
-
-/// Helper function to create token client + admin + address
-fn create_token_contract<'a>(
-    env: &Env,
-    admin: &Address,
-) -> (token::Client<'a>, token::StellarAssetClient<'a>, Address) {
-    let (token_admin, token_address) = create_test_token(env, admin);
-    let token_client = token::Client::new(env, &token_address);
-    (token_client, token_admin, token_address)
-}
-
 #[test]
 fn test_set_token_fee_valid() {
     let env = Env::default();
@@ -40,7 +27,7 @@ fn test_set_token_fee_valid() {
 
     let treasury = Address::generate(&env);
     let admin = Address::generate(&env);
-    client.initialize(&treasury, &Some(50)); // 0.5% default
+    init_contract(&env, &client, &treasury, &Some(50)); // 0.5% default
 
     let (_token_client, _token_admin, token_address) = create_token_contract(&env, &admin);
 
@@ -59,7 +46,7 @@ fn test_set_token_fee_invalid_fee_too_high() {
 
     let treasury = Address::generate(&env);
     let admin = Address::generate(&env);
-    client.initialize(&treasury, &Some(50));
+    init_contract(&env, &client, &treasury, &Some(50));
 
     let (_token_client, _token_admin, token_address) = create_token_contract(&env, &admin);
 
@@ -77,7 +64,7 @@ fn test_set_escrow_fee_valid() {
     let client = VaultixEscrowClient::new(&env, &contract_id);
 
     let treasury = Address::generate(&env);
-    client.initialize(&treasury, &Some(50)); // 0.5% default
+    init_contract(&env, &client, &treasury, &Some(50)); // 0.5% default
 
     let escrow_id = 1u64;
 
@@ -95,7 +82,7 @@ fn test_set_escrow_fee_invalid_fee_too_high() {
     let client = VaultixEscrowClient::new(&env, &contract_id);
 
     let treasury = Address::generate(&env);
-    client.initialize(&treasury, &Some(50));
+    init_contract(&env, &client, &treasury, &Some(50));
 
     let escrow_id = 1u64;
 
@@ -113,9 +100,10 @@ fn test_release_milestone_uses_global_fee_by_default() {
     let client = VaultixEscrowClient::new(&env, &contract_id);
 
     let treasury = Address::generate(&env);
-    client.initialize(&treasury, &Some(100)); // 1% fee
+    init_contract(&env, &client, &treasury, &Some(100)); // 1% fee
 
     let depositor = Address::generate(&env);
+    let operator = client.get_role(&Role::Operator);
     let recipient = Address::generate(&env);
     let admin = Address::generate(&env);
 
@@ -132,6 +120,8 @@ fn test_release_milestone_uses_global_fee_by_default() {
         },
     ];
 
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -154,7 +144,7 @@ fn test_release_milestone_uses_global_fee_by_default() {
     let expected_payout = 10_000i128 - expected_fee;
 
     assert_eq!(token_client.balance(&recipient), expected_payout);
-    assert_eq!(token_client.balance(&treasury), expected_fee);
+    assert_eq!(client.get_accrued_fees(&token_address), expected_fee);
 }
 
 #[test]
@@ -166,9 +156,10 @@ fn test_release_milestone_uses_token_fee_override() {
     let client = VaultixEscrowClient::new(&env, &contract_id);
 
     let treasury = Address::generate(&env);
-    client.initialize(&treasury, &Some(50)); // 0.5% global fee
+    init_contract(&env, &client, &treasury, &Some(50)); // 0.5% global fee
 
     let depositor = Address::generate(&env);
+    let operator = client.get_role(&Role::Operator);
     let recipient = Address::generate(&env);
     let admin = Address::generate(&env);
 
@@ -177,6 +168,7 @@ fn test_release_milestone_uses_token_fee_override() {
 
     // Set token-specific fee to 200 bps (2%)
     client.set_token_fee(&token_address, &200);
+    execute_queued(&env, &client);
 
     let escrow_id = 1u64;
     let milestones = vec![
@@ -188,6 +180,8 @@ fn test_release_milestone_uses_token_fee_override() {
         },
     ];
 
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -210,7 +204,7 @@ fn test_release_milestone_uses_token_fee_override() {
     let expected_payout = 10_000i128 - expected_fee;
 
     assert_eq!(token_client.balance(&recipient), expected_payout);
-    assert_eq!(token_client.balance(&treasury), expected_fee);
+    assert_eq!(client.get_accrued_fees(&token_address), expected_fee);
 }
 
 #[test]
@@ -222,9 +216,10 @@ fn test_release_milestone_uses_escrow_fee_override() {
     let client = VaultixEscrowClient::new(&env, &contract_id);
 
     let treasury = Address::generate(&env);
-    client.initialize(&treasury, &Some(50)); // 0.5% global fee
+    init_contract(&env, &client, &treasury, &Some(50)); // 0.5% global fee
 
     let depositor = Address::generate(&env);
+    let operator = client.get_role(&Role::Operator);
     let recipient = Address::generate(&env);
     let admin = Address::generate(&env);
 
@@ -233,11 +228,13 @@ fn test_release_milestone_uses_escrow_fee_override() {
 
     // Set token-specific fee to 100 bps (1%)
     client.set_token_fee(&token_address, &100);
+    execute_queued(&env, &client);
 
     let escrow_id = 1u64;
 
     // Set escrow-specific fee to 300 bps (3%) - highest priority
     client.set_escrow_fee(&escrow_id, &300);
+    execute_queued(&env, &client);
 
     let milestones = vec![
         &env,
@@ -248,6 +245,8 @@ fn test_release_milestone_uses_escrow_fee_override() {
         },
     ];
 
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -270,7 +269,7 @@ fn test_release_milestone_uses_escrow_fee_override() {
     let expected_payout = 10_000i128 - expected_fee;
 
     assert_eq!(token_client.balance(&recipient), expected_payout);
-    assert_eq!(token_client.balance(&treasury), expected_fee);
+    assert_eq!(client.get_accrued_fees(&token_address), expected_fee);
 }
 
 #[test]
@@ -282,9 +281,10 @@ fn test_fee_precedence_escrow_over_token_and_global() {
     let client = VaultixEscrowClient::new(&env, &contract_id);
 
     let treasury = Address::generate(&env);
-    client.initialize(&treasury, &Some(50)); // 0.5% global
+    init_contract(&env, &client, &treasury, &Some(50)); // 0.5% global
 
     let depositor = Address::generate(&env);
+    let operator = client.get_role(&Role::Operator);
     let recipient = Address::generate(&env);
     let admin = Address::generate(&env);
 
@@ -293,10 +293,12 @@ fn test_fee_precedence_escrow_over_token_and_global() {
 
     // Set token fee to 100 bps
     client.set_token_fee(&token_address, &100);
+    execute_queued(&env, &client);
 
     let escrow_id = 1u64;
     // Set escrow fee to 250 bps (should override token and global)
     client.set_escrow_fee(&escrow_id, &250);
+    execute_queued(&env, &client);
 
     let milestones = vec![
         &env,
@@ -307,6 +309,8 @@ fn test_fee_precedence_escrow_over_token_and_global() {
         },
     ];
 
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -326,7 +330,7 @@ fn test_fee_precedence_escrow_over_token_and_global() {
     let expected_payout = 10_000i128 - expected_fee;
 
     assert_eq!(token_client.balance(&recipient), expected_payout);
-    assert_eq!(token_client.balance(&treasury), expected_fee);
+    assert_eq!(client.get_accrued_fees(&token_address), expected_fee);
 }
 
 #[test]
@@ -338,9 +342,10 @@ fn test_cancel_escrow_uses_token_fee_override() {
     let client = VaultixEscrowClient::new(&env, &contract_id);
 
     let treasury = Address::generate(&env);
-    client.initialize(&treasury, &Some(50)); // 0.5% global fee
+    init_contract(&env, &client, &treasury, &Some(50)); // 0.5% global fee
 
     let depositor = Address::generate(&env);
+    let operator = client.get_role(&Role::Operator);
     let recipient = Address::generate(&env);
     let admin = Address::generate(&env);
 
@@ -349,6 +354,7 @@ fn test_cancel_escrow_uses_token_fee_override() {
 
     // Set token-specific fee to 200 bps (2%)
     client.set_token_fee(&token_address, &200);
+    execute_queued(&env, &client);
 
     let escrow_id = 1u64;
     let milestones = vec![
@@ -360,6 +366,8 @@ fn test_cancel_escrow_uses_token_fee_override() {
         },
     ];
 
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -380,7 +388,7 @@ fn test_cancel_escrow_uses_token_fee_override() {
     let expected_refund = 10_000i128 - expected_fee;
 
     assert_eq!(token_client.balance(&depositor), expected_refund);
-    assert_eq!(token_client.balance(&treasury), expected_fee);
+    assert_eq!(client.get_accrued_fees(&token_address), expected_fee);
 }
 
 #[test]
@@ -392,9 +400,10 @@ fn test_refund_expired_uses_escrow_fee_override() {
     let client = VaultixEscrowClient::new(&env, &contract_id);
 
     let treasury = Address::generate(&env);
-    client.initialize(&treasury, &Some(50)); // 0.5% global fee
+    init_contract(&env, &client, &treasury, &Some(50)); // 0.5% global fee
 
     let depositor = Address::generate(&env);
+    let operator = client.get_role(&Role::Operator);
     let recipient = Address::generate(&env);
     let admin = Address::generate(&env);
 
@@ -405,6 +414,7 @@ fn test_refund_expired_uses_escrow_fee_override() {
 
     // Set escrow fee to 500 bps (5%)
     client.set_escrow_fee(&escrow_id, &500);
+    execute_queued(&env, &client);
 
     let milestones = vec![
         &env,
@@ -416,6 +426,8 @@ fn test_refund_expired_uses_escrow_fee_override() {
     ];
 
     let deadline = env.ledger().timestamp() + 1; // Set a very short deadline
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -441,7 +453,7 @@ fn test_refund_expired_uses_escrow_fee_override() {
     let expected_refund = 10_000i128 - expected_fee;
 
     assert_eq!(token_client.balance(&depositor), expected_refund);
-    assert_eq!(token_client.balance(&treasury), expected_fee);
+    assert_eq!(client.get_accrued_fees(&token_address), expected_fee);
 }
 
 #[test]
@@ -453,7 +465,8 @@ fn test_zero_fee_valid() {
     let client = VaultixEscrowClient::new(&env, &contract_id);
 
     let treasury = Address::generate(&env);
-    client.initialize(&treasury, &Some(50));
+    init_contract(&env, &client, &treasury, &Some(50));
+    let operator = client.get_role(&Role::Operator);
 
     let depositor = Address::generate(&env);
     let recipient = Address::generate(&env);
@@ -464,6 +477,7 @@ fn test_zero_fee_valid() {
 
     // Set token fee to zero
     client.set_token_fee(&token_address, &0);
+    execute_queued(&env, &client);
 
     let escrow_id = 1u64;
     let milestones = vec![
@@ -475,6 +489,8 @@ fn test_zero_fee_valid() {
         },
     ];
 
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -491,7 +507,7 @@ fn test_zero_fee_valid() {
 
     // With zero fee, recipient gets full amount
     assert_eq!(token_client.balance(&recipient), 10_000i128);
-    assert_eq!(token_client.balance(&treasury), 0i128);
+    assert_eq!(client.get_accrued_fees(&token_address), 0i128);
 }
 
 #[test]
@@ -503,7 +519,7 @@ fn test_max_fee_10000_bps_valid() {
     let client = VaultixEscrowClient::new(&env, &contract_id);
 
     let treasury = Address::generate(&env);
-    client.initialize(&treasury, &Some(50));
+    init_contract(&env, &client, &treasury, &Some(50));
 
     let admin = Address::generate(&env);
     let (_token_client, _token_admin, token_address) = create_token_contract(&env, &admin);
@@ -512,3 +528,676 @@ fn test_max_fee_10000_bps_valid() {
     let result = client.try_set_token_fee(&token_address, &10000);
     assert!(result.is_ok());
 }
+
+#[test]
+fn test_withdraw_fees_transfers_accrued_balance() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, VaultixEscrow);
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    init_contract(&env, &client, &treasury, &Some(100)); // 1% fee
+
+    let depositor = Address::generate(&env);
+    let operator = client.get_role(&Role::Operator);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let fee_sink = Address::generate(&env);
+
+    let (token_client, token_admin, token_address) = create_token_contract(&env, &admin);
+    token_admin.mint(&depositor, &10_000);
+
+    let escrow_id = 1u64;
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 4_000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Phase1"),
+        },
+        Milestone {
+            amount: 6_000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Phase2"),
+        },
+    ];
+
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &(env.ledger().timestamp() + 3600),
+    );
+    token_client.approve(&depositor, &contract_id, &10_000, &200);
+    client.deposit_funds(&escrow_id);
+    client.release_milestone(&escrow_id, &0);
+
+    // Fee stays in the contract until the treasury pulls it
+    assert_eq!(client.get_accrued_fees(&token_address), 40);
+    assert_eq!(token_client.balance(&treasury), 0);
+    assert_eq!(token_client.balance(&contract_id), 6_040);
+
+    client.withdraw_fees(&token_address, &30, &fee_sink);
+
+    assert_eq!(token_client.balance(&fee_sink), 30);
+    assert_eq!(client.get_accrued_fees(&token_address), 10);
+    assert_eq!(token_client.balance(&contract_id), 6_010);
+}
+
+#[test]
+fn test_withdraw_fees_cannot_touch_escrowed_principal() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, VaultixEscrow);
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    init_contract(&env, &client, &treasury, &Some(100));
+    let operator = client.get_role(&Role::Operator);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+
+    let (token_client, token_admin, token_address) = create_token_contract(&env, &admin);
+    token_admin.mint(&depositor, &10_000);
+
+    let escrow_id = 1u64;
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 10_000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Work"),
+        },
+    ];
+
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &(env.ledger().timestamp() + 3600),
+    );
+    token_client.approve(&depositor, &contract_id, &10_000, &200);
+    client.deposit_funds(&escrow_id);
+
+    // Contract holds 10_000 of principal but no fees have accrued yet
+    let result = client.try_withdraw_fees(&token_address, &1, &treasury);
+    assert_eq!(result, Err(Ok(Error::InsufficientAccruedFees)));
+
+    let result = client.try_withdraw_fees(&token_address, &0, &treasury);
+    assert_eq!(result, Err(Ok(Error::ZeroAmount)));
+
+    assert_eq!(token_client.balance(&contract_id), 10_000);
+}
+
+#[test]
+fn test_depositor_pays_fee_recipient_receives_full_amount() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, VaultixEscrow);
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    init_contract(&env, &client, &treasury, &Some(100)); // 1% fee
+
+    let depositor = Address::generate(&env);
+    let operator = client.get_role(&Role::Operator);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+
+    let (token_client, token_admin, token_address) = create_token_contract(&env, &admin);
+    token_admin.mint(&depositor, &10_100);
+
+    let escrow_id = 1u64;
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 4_000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Phase1"),
+        },
+        Milestone {
+            amount: 6_000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Phase2"),
+        },
+    ];
+
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &(env.ledger().timestamp() + 3600),
+    );
+    client.set_fee_payer(&escrow_id, &FeePayer::Depositor);
+
+    token_client.approve(&depositor, &contract_id, &10_100, &200);
+    client.deposit_funds(&escrow_id);
+
+    // Principal plus the projected 1% fee is pulled up front
+    assert_eq!(token_client.balance(&depositor), 0);
+    assert_eq!(token_client.balance(&contract_id), 10_100);
+    assert_eq!(client.get_escrow(&escrow_id).fee_reserve, 100);
+
+    client.release_milestone(&escrow_id, &0);
+    client.release_milestone(&escrow_id, &1);
+
+    assert_eq!(token_client.balance(&recipient), 10_000);
+    assert_eq!(client.get_accrued_fees(&token_address), 100);
+    assert_eq!(client.get_escrow(&escrow_id).fee_reserve, 0);
+}
+
+#[test]
+fn test_depositor_pays_fee_refund_returns_unused_reserve() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, VaultixEscrow);
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    init_contract(&env, &client, &treasury, &Some(100)); // 1% fee
+
+    let depositor = Address::generate(&env);
+    let operator = client.get_role(&Role::Operator);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+
+    let (token_client, token_admin, token_address) = create_token_contract(&env, &admin);
+    token_admin.mint(&depositor, &10_100);
+
+    let escrow_id = 1u64;
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 4_000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Phase1"),
+        },
+        Milestone {
+            amount: 6_000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Phase2"),
+        },
+    ];
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &deadline,
+    );
+    client.set_fee_payer(&escrow_id, &FeePayer::Depositor);
+
+    token_client.approve(&depositor, &contract_id, &10_100, &200);
+    client.deposit_funds(&escrow_id);
+
+    // First milestone consumes 40 of the 100 reserved
+    client.release_milestone(&escrow_id, &0);
+    assert_eq!(token_client.balance(&recipient), 4_000);
+
+    env.ledger().with_mut(|li| li.timestamp = deadline + 1);
+    client.refund_expired(&escrow_id, &depositor);
+
+    // Remaining 6_000 is charged 60 from the reserve; principal is refunded in full
+    assert_eq!(token_client.balance(&depositor), 6_000);
+    assert_eq!(client.get_accrued_fees(&token_address), 100);
+    assert_eq!(token_client.balance(&contract_id), 100);
+}
+
+#[test]
+fn test_set_fee_payer_rejected_after_funding() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, VaultixEscrow);
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    init_contract(&env, &client, &treasury, &Some(100));
+    let operator = client.get_role(&Role::Operator);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+
+    let (token_client, token_admin, token_address) = create_token_contract(&env, &admin);
+    token_admin.mint(&depositor, &10_000);
+
+    let escrow_id = 1u64;
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 10_000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Work"),
+        },
+    ];
+
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &(env.ledger().timestamp() + 3600),
+    );
+    token_client.approve(&depositor, &contract_id, &10_000, &200);
+    client.deposit_funds(&escrow_id);
+
+    let result = client.try_set_fee_payer(&escrow_id, &FeePayer::Depositor);
+    assert_eq!(result, Err(Ok(Error::EscrowAlreadyFunded)));
+}
+
+#[test]
+fn test_fee_snapshot_ignores_later_token_fee_change() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, VaultixEscrow);
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    init_contract(&env, &client, &treasury, &Some(100)); // 1% fee
+
+    let depositor = Address::generate(&env);
+    let operator = client.get_role(&Role::Operator);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+
+    let (token_client, token_admin, token_address) = create_token_contract(&env, &admin);
+    token_admin.mint(&depositor, &10_000);
+
+    let escrow_id = 1u64;
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 10_000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Work"),
+        },
+    ];
+
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &(env.ledger().timestamp() + 3600),
+    );
+    assert_eq!(client.get_escrow(&escrow_id).fee_bps, 100);
+
+    token_client.approve(&depositor, &contract_id, &10_000, &200);
+    client.deposit_funds(&escrow_id);
+
+    // Raising the token fee after creation must not affect this escrow
+    client.set_token_fee(&token_address, &500);
+    execute_queued(&env, &client);
+    client.release_milestone(&escrow_id, &0);
+
+    assert_eq!(token_client.balance(&recipient), 9_900);
+    assert_eq!(client.get_accrued_fees(&token_address), 100);
+}
+
+#[test]
+fn test_global_fee_change_is_timelocked() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, VaultixEscrow);
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+    client.init(
+        &admin,
+        &operator,
+        &arbitrator,
+        &treasury,
+        &Some(50),
+        &MIN_GOVERNANCE_DELAY,
+    );
+
+    client.set_fee_change_delay(&86_400);
+    execute_queued(&env, &client);
+    assert_eq!(client.get_fee_change_delay(), 86_400);
+
+    client.update_fee(&operator, &200);
+    execute_queued(&env, &client);
+
+    // Nothing changes until the delay elapses
+    let eta = env.ledger().timestamp() + 86_400;
+    let pending = client.get_pending_fee_change(&None).unwrap();
+    assert_eq!(pending.fee_bps, 200);
+    assert_eq!(pending.eta, eta);
+    assert_eq!(client.get_config().1, 50);
+
+    let result = client.try_apply_fee_change(&None);
+    assert_eq!(result, Err(Ok(Error::TimelockNotReady)));
+
+    env.ledger().with_mut(|li| li.timestamp = eta);
+    client.apply_fee_change(&None);
+
+    assert_eq!(client.get_config().1, 200);
+    assert_eq!(client.get_pending_fee_change(&None), None);
+
+    let result = client.try_apply_fee_change(&None);
+    assert_eq!(result, Err(Ok(Error::NoPendingFeeChange)));
+}
+
+#[test]
+fn test_escrow_fee_override_cannot_raise_funded_escrow() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, VaultixEscrow);
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    init_contract(&env, &client, &treasury, &Some(100));
+    let operator = client.get_role(&Role::Operator);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+
+    let (token_client, token_admin, token_address) = create_token_contract(&env, &admin);
+    token_admin.mint(&depositor, &10_000);
+
+    let escrow_id = 1u64;
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 10_000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Work"),
+        },
+    ];
+
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &(env.ledger().timestamp() + 3600),
+    );
+    token_client.approve(&depositor, &contract_id, &10_000, &200);
+    client.deposit_funds(&escrow_id);
+
+    let result = client.try_set_escrow_fee(&escrow_id, &300);
+    assert_eq!(result, Err(Ok(Error::InvalidFeeConfiguration)));
+
+    // Lowering the fee of a funded escrow is still allowed
+    client.set_escrow_fee(&escrow_id, &0);
+    execute_queued(&env, &client);
+    client.release_milestone(&escrow_id, &0);
+    assert_eq!(token_client.balance(&recipient), 10_000);
+}
+
+#[test]
+fn test_fee_discount_applies_when_either_party_listed() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, VaultixEscrow);
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+    client.init(
+        &admin,
+        &operator,
+        &arbitrator,
+        &treasury,
+        &Some(200),
+        &MIN_GOVERNANCE_DELAY,
+    ); // 2% fee
+
+    let depositor = Address::generate(&env);
+    let partner = Address::generate(&env);
+    let internal = Address::generate(&env);
+
+    let (_token_client, _token_admin, token_address) = create_token_contract(&env, &admin);
+
+    // 50% off for the partner, full exemption for the internal account
+    client.add_fee_discount(&operator, &partner, &5_000, &None);
+    client.add_fee_discount(&operator, &internal, &10_000, &None);
+    execute_queued(&env, &client);
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 10_000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Work"),
+        },
+    ];
+    let deadline = env.ledger().timestamp() + 3600;
+
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
+    client.create_escrow(
+        &1,
+        &depositor,
+        &partner,
+        &token_address,
+        &milestones,
+        &deadline,
+    );
+    client.create_escrow(
+        &2,
+        &internal,
+        &depositor,
+        &token_address,
+        &milestones,
+        &deadline,
+    );
+
+    assert_eq!(client.get_escrow(&1).fee_bps, 100);
+    assert_eq!(client.get_escrow(&2).fee_bps, 0);
+
+    // Removing the discount only affects escrows created afterwards
+    client.remove_fee_discount(&operator, &partner);
+    execute_queued(&env, &client);
+    client.create_escrow(
+        &3,
+        &depositor,
+        &partner,
+        &token_address,
+        &milestones,
+        &deadline,
+    );
+
+    assert_eq!(client.get_escrow(&1).fee_bps, 100);
+    assert_eq!(client.get_escrow(&3).fee_bps, 200);
+    assert_eq!(client.get_fee_discount(&partner), None);
+
+    let result = client.try_remove_fee_discount(&operator, &partner);
+    assert_eq!(result, Err(Ok(Error::FeeDiscountNotFound)));
+}
+
+#[test]
+fn test_fee_discount_expires() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, VaultixEscrow);
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+    client.init(
+        &admin,
+        &operator,
+        &arbitrator,
+        &treasury,
+        &Some(200),
+        &MIN_GOVERNANCE_DELAY,
+    );
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let (_token_client, _token_admin, token_address) = create_token_contract(&env, &admin);
+
+    // The discount lapses an hour after it takes effect
+    let valid_until = env.ledger().timestamp() + MIN_GOVERNANCE_DELAY + 3_600;
+    client.add_fee_discount(&operator, &depositor, &10_000, &Some(valid_until));
+    execute_queued(&env, &client);
+
+    let result = client.try_add_fee_discount(&operator, &recipient, &10_001, &None);
+    assert_eq!(result, Err(Ok(Error::InvalidFeeConfiguration)));
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 10_000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Work"),
+        },
+    ];
+
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
+    client.create_escrow(
+        &1,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &5_000,
+    );
+    assert_eq!(client.get_escrow(&1).fee_bps, 0);
+
+    env.ledger().with_mut(|li| li.timestamp = valid_until + 1);
+    client.create_escrow(
+        &2,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &5_000,
+    );
+    assert_eq!(client.get_escrow(&2).fee_bps, 200);
+}
+
+#[test]
+fn test_quotes_match_settlement_amounts() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, VaultixEscrow);
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    init_contract(&env, &client, &treasury, &Some(150)); // 1.5% fee
+
+    let depositor = Address::generate(&env);
+    let operator = client.get_role(&Role::Operator);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+
+    let (token_client, token_admin, token_address) = create_token_contract(&env, &admin);
+    token_admin.mint(&depositor, &10_000);
+
+    let escrow_id = 1u64;
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 3_333,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Phase1"),
+        },
+        Milestone {
+            amount: 6_667,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Phase2"),
+        },
+    ];
+
+    let deadline = env.ledger().timestamp() + 3600;
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &deadline,
+    );
+
+    // Unfunded escrows cancel without transfers
+    assert_eq!(
+        client.quote_cancel(&escrow_id),
+        SettlementQuote {
+            fee_bps: 0,
+            fee: 0,
+            payout: 0,
+            refund: 0,
+        }
+    );
+
+    token_client.approve(&depositor, &contract_id, &10_000, &200);
+    client.deposit_funds(&escrow_id);
+
+    let cancel_quote = client.quote_cancel(&escrow_id);
+    assert_eq!(cancel_quote.fee, 150);
+    assert_eq!(cancel_quote.refund, 9_850);
+
+    let release_quote = client.quote_release(&escrow_id, &0);
+    assert_eq!(release_quote.fee_bps, 150);
+    client.release_milestone(&escrow_id, &0);
+    assert_eq!(token_client.balance(&recipient), release_quote.payout);
+    assert_eq!(client.get_accrued_fees(&token_address), release_quote.fee);
+
+    let result = client.try_quote_release(&escrow_id, &0);
+    assert_eq!(result, Err(Ok(Error::MilestoneAlreadyReleased)));
+
+    let result = client.try_quote_cancel(&escrow_id);
+    assert_eq!(result, Err(Ok(Error::MilestoneAlreadyReleased)));
+
+    let refund_quote = client.quote_refund(&escrow_id);
+    env.ledger().with_mut(|li| li.timestamp = deadline + 1);
+    client.refund_expired(&escrow_id, &depositor);
+    assert_eq!(token_client.balance(&depositor), refund_quote.refund);
+    assert_eq!(
+        client.get_accrued_fees(&token_address),
+        release_quote.fee + refund_quote.fee
+    );
+}
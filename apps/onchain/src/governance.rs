@@ -0,0 +1,307 @@
+// governance.rs
+//! Timelocked queue for configuration changes.
+//!
+//! Configuration changes are queued with an ETA of now plus the governance
+//! delay, can be inspected and cancelled while pending, and can be executed by
+//! anyone once the ETA has passed. Upgrade proposals and sweeps are queued like
+//! configuration changes. Changes that only restrict (pausing, blocking an
+//! address, revoking a role or removing a token) and cancellations take the fast
+//! path and always apply immediately. The delay is chosen at `init` and can
+//! never be set below `MIN_GOVERNANCE_DELAY`.
+#[cfg(test)]
+use crate::__VaultixEscrow_fn_set_registry;
+use crate::events::{
+    publish, publish_for, ChangeCancelled, ChangeExecuted, ChangeQueued, GovernanceDelayUpdated,
+};
+use crate::{
+    apply_emergency_exit_delay, apply_escrow_fee, apply_fee_change_delay, apply_fee_discount,
+    apply_fee_discount_removal, apply_operation_paused, apply_paused, apply_role_transfer_cancel,
+    apply_role_transfer_proposal, apply_sweep, apply_token_limits, apply_token_paused,
+    apply_token_removal,
+    archive::apply_retention_period,
+    credits::apply_credit_mode,
+    ensure_migrated, get_admin, get_persistent, get_stored_config,
+    multisig::{
+        apply_enable_multisig, ensure_multisig_disabled, set_multisig_config,
+        validate_multisig_config,
+    },
+    perform_upgrade,
+    roles::{apply_grant_role, apply_revoke_role, apply_role_admin},
+    schedule_fee_change, schedule_upgrade,
+    screening::{apply_address_blocked, apply_screening_contract},
+    set_persistent, set_stored_config, unschedule_upgrade, validate_escrow_fee,
+    validate_fee_discount, validate_token_limits, AdminAction, DataKey, Error, VaultixEscrow,
+    VaultixEscrowClient, BPS_DENOMINATOR, DEFAULT_GOVERNANCE_DELAY, MIN_GOVERNANCE_DELAY,
+};
+use soroban_sdk::{contractimpl, contracttype, Address, Env, Vec};
+
+/// Maximum number of changes that can be pending at once, keeping the list of
+/// queued change IDs small enough to load on every submission.
+pub(crate) const MAX_QUEUED_CHANGES: u32 = 20;
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QueuedChange {
+    pub action: AdminAction,
+    pub queued_by: Address,
+    pub eta: u64,
+}
+
+#[contractimpl]
+impl VaultixEscrow {
+    /// Queue a change of the governance delay. Only admin can call.
+    /// The change itself waits out the current delay, so shortening the delay
+    /// is as visible as any other queued change.
+    pub fn set_governance_delay(env: Env, delay: u64) -> Result<(), Error> {
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+        ensure_multisig_disabled(&env)?;
+
+        submit_action(&env, AdminAction::SetGovernanceDelay(delay), admin)
+    }
+
+    pub fn get_governance_delay(env: Env) -> u64 {
+        get_governance_delay(&env)
+    }
+
+    /// Execute a queued change whose ETA has passed.
+    /// Anyone can call this function.
+    pub fn execute_queued_change(env: Env, change_id: u64) -> Result<(), Error> {
+        let change = load_queued_change(&env, change_id)?;
+        if env.ledger().timestamp() < change.eta {
+            return Err(Error::TimelockNotReady);
+        }
+        remove_queued_change(&env, change_id);
+
+        apply_action(&env, change.action, change.queued_by.clone())?;
+
+        publish_for(
+            &env,
+            change_id,
+            ChangeExecuted {
+                queued_by: change.queued_by,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Cancel a queued change. Only admin can call.
+    pub fn cancel_queued_change(env: Env, change_id: u64) -> Result<(), Error> {
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+        ensure_multisig_disabled(&env)?;
+
+        submit_action(&env, AdminAction::CancelQueuedChange(change_id), admin)
+    }
+
+    pub fn get_queued_change(env: Env, change_id: u64) -> Option<QueuedChange> {
+        get_persistent(&env, &DataKey::QueuedChange(change_id))
+    }
+
+    /// Returns the IDs of all pending queued changes, oldest first.
+    /// At most `MAX_QUEUED_CHANGES` changes can be pending at once.
+    pub fn get_queued_changes(env: Env) -> Vec<u64> {
+        get_queued_change_ids(&env)
+    }
+}
+
+fn get_governance_delay(env: &Env) -> u64 {
+    get_stored_config(env)
+        .map(|config| config.governance_delay)
+        .unwrap_or(DEFAULT_GOVERNANCE_DELAY)
+}
+
+fn apply_governance_delay(env: &Env, delay: u64) -> Result<(), Error> {
+    let mut config = get_stored_config(env).ok_or(Error::AdminNotInitialized)?;
+    let old_delay = config.governance_delay;
+    config.governance_delay = delay;
+    set_stored_config(env, &config);
+
+    publish(
+        env,
+        GovernanceDelayUpdated {
+            old_delay,
+            new_delay: delay,
+        },
+    );
+
+    Ok(())
+}
+
+fn get_queued_change_ids(env: &Env) -> Vec<u64> {
+    env.storage()
+        .instance()
+        .get(&DataKey::QueuedChangeIds)
+        .unwrap_or(Vec::new(env))
+}
+
+fn load_queued_change(env: &Env, change_id: u64) -> Result<QueuedChange, Error> {
+    get_persistent(env, &DataKey::QueuedChange(change_id)).ok_or(Error::ProposalNotFound)
+}
+
+fn remove_queued_change(env: &Env, change_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::QueuedChange(change_id));
+    let mut ids = get_queued_change_ids(env);
+    if let Some(index) = ids.first_index_of(change_id) {
+        ids.remove(index);
+    }
+    env.storage()
+        .instance()
+        .set(&DataKey::QueuedChangeIds, &ids);
+}
+
+/// Whether `action` must wait out the governance delay.
+/// Only pauses, restrictions and cancellations take the fast path. Executing an
+/// upgrade skips the queue because its proposal was queued and timelocked.
+fn requires_queue(action: &AdminAction) -> bool {
+    match action {
+        AdminAction::SetPaused(paused)
+        | AdminAction::SetOperationPaused(_, paused)
+        | AdminAction::SetTokenPaused(_, paused) => !paused,
+        AdminAction::SetAddressBlocked(_, blocked) => !blocked,
+        AdminAction::RevokeRole(_, _)
+        | AdminAction::RemoveAllowedToken(_)
+        | AdminAction::CancelQueuedChange(_)
+        | AdminAction::CancelRoleTransfer(_)
+        | AdminAction::CancelUpgrade
+        | AdminAction::ExecuteUpgrade => false,
+        _ => true,
+    }
+}
+
+/// Applies `action` now, or queues it when it must wait out the governance delay.
+/// `actor` is recorded as the address responsible for the change.
+pub(crate) fn submit_action(env: &Env, action: AdminAction, actor: Address) -> Result<(), Error> {
+    // Reject invalid parameters up front rather than when the change is executed
+    match &action {
+        AdminAction::UpdateFee(fee_bps) | AdminAction::SetTokenFee(_, fee_bps)
+            if !(0..=BPS_DENOMINATOR).contains(fee_bps) =>
+        {
+            return Err(Error::InvalidFeeConfiguration);
+        }
+        AdminAction::SetEscrowFee(escrow_id, fee_bps) => {
+            validate_escrow_fee(env, *escrow_id, *fee_bps)?
+        }
+        AdminAction::AddFeeDiscount(_, discount) => validate_fee_discount(env, discount)?,
+        AdminAction::AllowToken(_, limits) => validate_token_limits(limits)?,
+        AdminAction::SetMultisig(config) | AdminAction::EnableMultisig(config) => {
+            validate_multisig_config(config)?
+        }
+        AdminAction::SetGovernanceDelay(delay) if *delay < MIN_GOVERNANCE_DELAY => {
+            return Err(Error::GovernanceDelayTooShort);
+        }
+        AdminAction::SweepExcess(_, _) => ensure_migrated(env)?,
+        _ => {}
+    }
+
+    if !requires_queue(&action) {
+        return apply_action(env, action, actor);
+    }
+    let mut ids = get_queued_change_ids(env);
+    if ids.len() >= MAX_QUEUED_CHANGES {
+        return Err(Error::VectorTooLarge);
+    }
+
+    let change_id: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::QueuedChangeCounter)
+        .unwrap_or(0);
+    env.storage()
+        .instance()
+        .set(&DataKey::QueuedChangeCounter, &(change_id + 1));
+
+    let eta = env
+        .ledger()
+        .timestamp()
+        .checked_add(get_governance_delay(env))
+        .ok_or(Error::TimelockNotReady)?;
+    let change = QueuedChange {
+        action,
+        queued_by: actor,
+        eta,
+    };
+    set_persistent(env, &DataKey::QueuedChange(change_id), &change);
+    ids.push_back(change_id);
+    env.storage()
+        .instance()
+        .set(&DataKey::QueuedChangeIds, &ids);
+
+    publish_for(
+        env,
+        change_id,
+        ChangeQueued {
+            action: change.action,
+            queued_by: change.queued_by,
+            eta,
+        },
+    );
+
+    Ok(())
+}
+
+fn apply_action(env: &Env, action: AdminAction, actor: Address) -> Result<(), Error> {
+    match action {
+        AdminAction::SetPaused(paused) => apply_paused(env, paused, actor),
+        AdminAction::SetOperationPaused(operation, paused) => {
+            apply_operation_paused(env, operation, paused, actor)
+        }
+        AdminAction::SetTokenPaused(token_address, paused) => {
+            apply_token_paused(env, token_address, paused, actor)
+        }
+        AdminAction::UpdateFee(fee_bps) => schedule_fee_change(env, None, fee_bps)?,
+        AdminAction::SetTokenFee(token_address, fee_bps) => {
+            schedule_fee_change(env, Some(token_address), fee_bps)?
+        }
+        AdminAction::SetEscrowFee(escrow_id, fee_bps) => apply_escrow_fee(env, escrow_id, fee_bps)?,
+        AdminAction::SetFeeChangeDelay(delay) => apply_fee_change_delay(env, delay)?,
+        AdminAction::ProposeUpgrade(wasm_hash) => schedule_upgrade(env, wasm_hash)?,
+        AdminAction::CancelUpgrade => unschedule_upgrade(env)?,
+        AdminAction::ExecuteUpgrade => perform_upgrade(env)?,
+        AdminAction::SetMultisig(config) => set_multisig_config(env, &config)?,
+        AdminAction::EnableMultisig(config) => apply_enable_multisig(env, &config)?,
+        AdminAction::SweepExcess(token_address, to) => {
+            apply_sweep(env, token_address, to)?;
+        }
+        AdminAction::GrantRole(role, account) => apply_grant_role(env, role, account, actor)?,
+        AdminAction::RevokeRole(role, account) => apply_revoke_role(env, role, account, actor),
+        AdminAction::SetRoleAdmin(role, admin_role) => apply_role_admin(env, role, admin_role),
+        AdminAction::ProposeRoleTransfer(role, new_addr) => {
+            apply_role_transfer_proposal(env, role, new_addr)?
+        }
+        AdminAction::SetGovernanceDelay(delay) => apply_governance_delay(env, delay)?,
+        AdminAction::CancelQueuedChange(change_id) => {
+            load_queued_change(env, change_id)?;
+            remove_queued_change(env, change_id);
+
+            publish_for(
+                env,
+                change_id,
+                ChangeCancelled {
+                    cancelled_by: actor,
+                },
+            );
+        }
+        AdminAction::CancelRoleTransfer(role) => apply_role_transfer_cancel(env, role)?,
+        AdminAction::SetEmergencyExitDelay(delay) => apply_emergency_exit_delay(env, delay)?,
+        AdminAction::AddFeeDiscount(addr, discount) => apply_fee_discount(env, addr, discount)?,
+        AdminAction::RemoveFeeDiscount(addr) => apply_fee_discount_removal(env, addr, actor)?,
+        AdminAction::AllowToken(token_address, limits) => {
+            apply_token_limits(env, token_address, limits)?
+        }
+        AdminAction::RemoveAllowedToken(token_address) => {
+            apply_token_removal(env, token_address, actor)?
+        }
+        AdminAction::SetScreeningContract(screener) => {
+            apply_screening_contract(env, Some(screener), actor)?
+        }
+        AdminAction::ClearScreeningContract => apply_screening_contract(env, None, actor)?,
+        AdminAction::SetAddressBlocked(addr, blocked) => apply_address_blocked(env, addr, blocked),
+        AdminAction::SetRetentionPeriod(period) => apply_retention_period(env, period)?,
+        AdminAction::SetCreditMode(enabled) => apply_credit_mode(env, enabled)?,
+    }
+    Ok(())
+}
@@ -0,0 +1,176 @@
+use crate::governance::MAX_QUEUED_CHANGES;
+use crate::test_fixtures::register_contract;
+use crate::{
+    AdminAction, Error, PausableOperation, Role, VaultixEscrow, VaultixEscrowClient,
+    MIN_GOVERNANCE_DELAY,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Env,
+};
+
+/// Helper function to register the contract with a one-hour governance delay
+fn setup_governance(env: &Env) -> (VaultixEscrowClient<'_>, Address) {
+    let client = register_contract(env, Some(50), 3_600);
+    assert_eq!(client.get_governance_delay(), 3_600);
+
+    let operator = client.get_role(&Role::Operator);
+    (client, operator)
+}
+
+#[test]
+fn test_fee_change_waits_for_governance_delay() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, operator) = setup_governance(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.update_fee(&operator, &100);
+    assert_eq!(client.get_config().1, 50);
+
+    assert_eq!(client.get_queued_changes(), vec![&env, 0u64]);
+    let change = client.get_queued_change(&0).unwrap();
+    assert_eq!(change.action, AdminAction::UpdateFee(100));
+    assert_eq!(change.queued_by, operator);
+    assert_eq!(change.eta, 1_000 + 3_600);
+
+    let result = client.try_execute_queued_change(&0);
+    assert_eq!(result, Err(Ok(Error::TimelockNotReady)));
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000 + 3_600);
+    client.execute_queued_change(&0);
+    assert_eq!(client.get_config().1, 100);
+    assert_eq!(client.get_queued_changes().len(), 0);
+
+    let result = client.try_execute_queued_change(&0);
+    assert_eq!(result, Err(Ok(Error::ProposalNotFound)));
+
+    // Invalid fees are rejected before they are queued
+    let result = client.try_update_fee(&operator, &10_001);
+    assert_eq!(result, Err(Ok(Error::InvalidFeeConfiguration)));
+}
+
+#[test]
+fn test_pause_is_immediate_but_unpause_is_queued() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, operator) = setup_governance(&env);
+
+    client.set_operation_paused(&operator, &PausableOperation::Create, &true);
+    assert!(client.is_paused(&PausableOperation::Create, &None));
+
+    client.set_operation_paused(&operator, &PausableOperation::Create, &false);
+    assert!(client.is_paused(&PausableOperation::Create, &None));
+    assert_eq!(client.get_queued_changes(), vec![&env, 0u64]);
+
+    client.cancel_queued_change(&0);
+    assert_eq!(client.get_queued_change(&0), None);
+    assert_eq!(client.get_queued_changes().len(), 0);
+    assert!(client.is_paused(&PausableOperation::Create, &None));
+}
+
+#[test]
+fn test_role_grant_is_queued() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup_governance(&env);
+    let admin = client.get_role(&Role::Admin);
+
+    let new_arbitrator = Address::generate(&env);
+    client.grant_role(&admin, &Role::Arbitrator, &new_arbitrator);
+    assert!(!client.has_role(&Role::Arbitrator, &new_arbitrator));
+
+    env.ledger().with_mut(|li| li.timestamp += 3_600);
+    client.execute_queued_change(&0);
+    assert!(client.has_role(&Role::Arbitrator, &new_arbitrator));
+
+    // Revocation is not delayed
+    client.revoke_role(&admin, &Role::Arbitrator, &new_arbitrator);
+    assert!(!client.has_role(&Role::Arbitrator, &new_arbitrator));
+    assert_eq!(client.get_queued_changes().len(), 0);
+}
+
+#[test]
+fn test_config_setters_are_queued_but_restrictions_are_immediate() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, operator) = setup_governance(&env);
+
+    let token_address = Address::generate(&env);
+    client.allow_token(&operator, &token_address, &1, &1_000);
+    client.set_credit_mode(&true);
+    client.set_retention_period(&60);
+    client.set_emergency_exit_delay(&60);
+    assert_eq!(client.get_token_limits(&token_address), None);
+    assert!(!client.is_credit_mode());
+    assert_eq!(client.get_queued_changes(), vec![&env, 0u64, 1, 2, 3]);
+
+    // Invalid parameters are rejected before they are queued
+    let result = client.try_allow_token(&operator, &token_address, &2_000, &1_000);
+    assert_eq!(result, Err(Ok(Error::AmountOutOfRange)));
+
+    env.ledger().with_mut(|li| li.timestamp += 3_600);
+    for change_id in 0..4u64 {
+        client.execute_queued_change(&change_id);
+    }
+    assert!(client.get_token_limits(&token_address).is_some());
+    assert!(client.is_credit_mode());
+    assert_eq!(client.get_retention_period(), 60);
+    assert_eq!(client.get_emergency_exit_delay(), 60);
+
+    let blocked = Address::generate(&env);
+    client.set_address_blocked(&operator, &blocked, &true);
+    assert!(!client.is_address_allowed(&blocked));
+    client.set_address_blocked(&operator, &blocked, &false);
+    assert!(!client.is_address_allowed(&blocked));
+
+    client.remove_allowed_token(&operator, &token_address);
+    assert_eq!(client.get_token_limits(&token_address), None);
+}
+
+#[test]
+fn test_governance_delay_has_a_minimum() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, VaultixEscrow);
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let result = client.try_init(
+        &admin,
+        &admin,
+        &admin,
+        &admin,
+        &None,
+        &(MIN_GOVERNANCE_DELAY - 1),
+    );
+    assert_eq!(result, Err(Ok(Error::GovernanceDelayTooShort)));
+
+    // The queue cannot be switched off later either
+    let (client, _) = setup_governance(&env);
+    let result = client.try_set_governance_delay(&0);
+    assert_eq!(result, Err(Ok(Error::GovernanceDelayTooShort)));
+    assert_eq!(client.get_queued_changes().len(), 0);
+}
+
+#[test]
+fn test_queue_is_bounded() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, operator) = setup_governance(&env);
+
+    for fee_bps in 0..MAX_QUEUED_CHANGES {
+        client.update_fee(&operator, &(fee_bps as i128));
+    }
+    let result = client.try_update_fee(&operator, &100);
+    assert_eq!(result, Err(Ok(Error::VectorTooLarge)));
+
+    // Restrictions still apply while the queue is full
+    client.set_paused(&operator, &true);
+    assert!(client.is_paused(&PausableOperation::Create, &None));
+
+    // Cancelling a change frees its slot
+    client.cancel_queued_change(&0);
+    client.update_fee(&operator, &100);
+    assert_eq!(client.get_queued_changes().len(), MAX_QUEUED_CHANGES);
+}
@@ -0,0 +1,100 @@
+use crate::test_fixtures::{milestones, setup};
+use crate::{EscrowStatus, VaultixEscrowClient};
+use soroban_sdk::{testutils::Address as _, token, vec, Address, Env};
+
+/// Helper function to create a single-milestone escrow between two parties
+fn create(
+    env: &Env,
+    client: &VaultixEscrowClient,
+    escrow_id: u64,
+    depositor: &Address,
+    recipient: &Address,
+    token_address: &Address,
+) {
+    client.create_escrow(
+        &escrow_id,
+        depositor,
+        recipient,
+        token_address,
+        &milestones(env, &[1_000]),
+        &1_706_400_000u64,
+    );
+}
+
+#[test]
+fn test_escrows_are_indexed_by_party() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_address) = setup(&env, None);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    create(&env, &client, 1, &alice, &bob, &token_address);
+    create(&env, &client, 2, &alice, &carol, &token_address);
+    create(&env, &client, 3, &bob, &carol, &token_address);
+
+    let page = client.get_escrows_by_depositor(&alice, &0, &10, &None);
+    assert_eq!(page.escrow_ids, vec![&env, 1u64, 2u64]);
+    assert_eq!(page.next_cursor, None);
+
+    let page = client.get_escrows_by_recipient(&carol, &0, &10, &None);
+    assert_eq!(page.escrow_ids, vec![&env, 2u64, 3u64]);
+
+    let page = client.get_escrows_by_depositor(&bob, &0, &10, &None);
+    assert_eq!(page.escrow_ids, vec![&env, 3u64]);
+    let page = client.get_escrows_by_recipient(&alice, &0, &10, &None);
+    assert_eq!(page.escrow_ids.len(), 0);
+}
+
+#[test]
+fn test_escrow_index_pagination_and_status_filter() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_address) = setup(&env, None);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    for escrow_id in 1..=5u64 {
+        create(
+            &env,
+            &client,
+            escrow_id,
+            &depositor,
+            &recipient,
+            &token_address,
+        );
+    }
+    token::StellarAssetClient::new(&env, &token_address).mint(&depositor, &2_000);
+    token::Client::new(&env, &token_address).approve(&depositor, &client.address, &2_000, &200);
+    client.deposit_funds(&2);
+    client.deposit_funds(&4);
+    client.raise_dispute(&2, &depositor);
+    client.raise_dispute(&4, &recipient);
+
+    let page = client.get_escrows_by_depositor(&depositor, &0, &2, &None);
+    assert_eq!(page.escrow_ids, vec![&env, 1u64, 2u64]);
+    assert_eq!(page.next_cursor, Some(2));
+
+    let page = client.get_escrows_by_depositor(&depositor, &2, &2, &None);
+    assert_eq!(page.escrow_ids, vec![&env, 3u64, 4u64]);
+    assert_eq!(page.next_cursor, Some(4));
+
+    let page = client.get_escrows_by_depositor(&depositor, &4, &2, &None);
+    assert_eq!(page.escrow_ids, vec![&env, 5u64]);
+    assert_eq!(page.next_cursor, None);
+
+    // A zero limit cannot be followed forever
+    let page = client.get_escrows_by_depositor(&depositor, &0, &0, &None);
+    assert_eq!(page.escrow_ids.len(), 0);
+    assert_eq!(page.next_cursor, None);
+
+    // Filtering applies to the scanned entries, so pages can be short
+    let page = client.get_escrows_by_recipient(&recipient, &0, &3, &Some(EscrowStatus::Disputed));
+    assert_eq!(page.escrow_ids, vec![&env, 2u64]);
+    assert_eq!(page.next_cursor, Some(3));
+
+    let page = client.get_escrows_by_recipient(&recipient, &3, &3, &Some(EscrowStatus::Disputed));
+    assert_eq!(page.escrow_ids, vec![&env, 4u64]);
+    assert_eq!(page.next_cursor, None);
+}
@@ -0,0 +1,132 @@
+// indexes.rs
+//! Per-address indexes of the escrows an address is party to.
+//!
+//! Every escrow is appended to its depositor's and its recipient's index when it
+//! is created. Each index entry is its own persistent entry keyed by position,
+//! so indexes grow without rewriting earlier entries and are read one page at
+//! a time.
+#[cfg(test)]
+use crate::__VaultixEscrow_fn_set_registry;
+use crate::archive::escrow_status;
+use crate::{
+    get_persistent, set_persistent, DataKey, EscrowStatus, VaultixEscrow, VaultixEscrowClient,
+};
+use soroban_sdk::{contractimpl, contracttype, Address, Env, Vec};
+
+/// Maximum number of index entries scanned by a single page request.
+const MAX_PAGE_SIZE: u32 = 50;
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EscrowPage {
+    pub escrow_ids: Vec<u64>,
+    pub next_cursor: Option<u32>, // None once the end of the index is reached
+}
+
+/// Which party's index to use.
+#[derive(Clone, Copy)]
+enum Party {
+    Depositor,
+    Recipient,
+}
+
+#[contractimpl]
+impl VaultixEscrow {
+    /// Returns escrows created by `depositor`, oldest first.
+    /// See `get_escrows_by_recipient` for the paging rules.
+    pub fn get_escrows_by_depositor(
+        env: Env,
+        depositor: Address,
+        cursor: u32,
+        limit: u32,
+        status: Option<EscrowStatus>,
+    ) -> EscrowPage {
+        get_page(&env, Party::Depositor, &depositor, cursor, limit, status)
+    }
+
+    /// Returns escrows paying `recipient`, oldest first.
+    ///
+    /// Scans up to `limit` index entries (at most `MAX_PAGE_SIZE`) starting at
+    /// `cursor` and returns those matching `status`, if given. A page may hold
+    /// fewer matches than it scanned; keep passing `next_cursor` until it is `None`.
+    /// A zero `limit` scans nothing and returns no `next_cursor`.
+    pub fn get_escrows_by_recipient(
+        env: Env,
+        recipient: Address,
+        cursor: u32,
+        limit: u32,
+        status: Option<EscrowStatus>,
+    ) -> EscrowPage {
+        get_page(&env, Party::Recipient, &recipient, cursor, limit, status)
+    }
+}
+
+/// Storage key for the number of entries in an address index
+fn get_index_count_key(party: Party, addr: &Address) -> DataKey {
+    match party {
+        Party::Depositor => DataKey::DepositorIndexLen(addr.clone()),
+        Party::Recipient => DataKey::RecipientIndexLen(addr.clone()),
+    }
+}
+
+/// Storage key for the entry at `position` in an address index
+fn get_index_entry_key(party: Party, addr: &Address, position: u32) -> DataKey {
+    match party {
+        Party::Depositor => DataKey::DepositorIndex(addr.clone(), position),
+        Party::Recipient => DataKey::RecipientIndex(addr.clone(), position),
+    }
+}
+
+fn get_index_count(env: &Env, party: Party, addr: &Address) -> u32 {
+    get_persistent(env, &get_index_count_key(party, addr)).unwrap_or(0)
+}
+
+fn append_to_index(env: &Env, party: Party, addr: &Address, escrow_id: u64) {
+    let count = get_index_count(env, party, addr);
+    set_persistent(env, &get_index_entry_key(party, addr, count), &escrow_id);
+    set_persistent(env, &get_index_count_key(party, addr), &(count + 1));
+}
+
+fn get_page(
+    env: &Env,
+    party: Party,
+    addr: &Address,
+    cursor: u32,
+    limit: u32,
+    status: Option<EscrowStatus>,
+) -> EscrowPage {
+    let count = get_index_count(env, party, addr);
+    let end = cursor.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+
+    let mut escrow_ids = Vec::new(env);
+    for position in cursor..end {
+        let escrow_id: u64 = match get_persistent(env, &get_index_entry_key(party, addr, position))
+        {
+            Some(escrow_id) => escrow_id,
+            None => continue,
+        };
+        let matches = match status {
+            None => true,
+            Some(status) => escrow_status(env, escrow_id) == Some(status),
+        };
+        if matches {
+            escrow_ids.push_back(escrow_id);
+        }
+    }
+
+    EscrowPage {
+        escrow_ids,
+        // An empty scan never advances, so it must not offer a cursor to follow
+        next_cursor: if cursor < end && end < count {
+            Some(end)
+        } else {
+            None
+        },
+    }
+}
+
+/// Records a new escrow in the indexes of both parties.
+pub(crate) fn index_escrow(env: &Env, escrow_id: u64, depositor: &Address, recipient: &Address) {
+    append_to_index(env, Party::Depositor, depositor, escrow_id);
+    append_to_index(env, Party::Recipient, recipient, escrow_id);
+}
@@ -2,33 +2,44 @@
 #![no_std]
 #![allow(unexpected_cfgs)]
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, Symbol,
-    Vec,
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, BytesN, Env,
+    IntoVal, Map, Symbol, TryFromVal, Val, Vec,
 };
 
-impl VaultixEscrow {
-    /// Secure contract upgrade function (Admin Proxy).
-    /// WARNING: Future upgrades MUST preserve storage layout (structs, enums, keys) to avoid corrupting state.
-    /// Only admin can call. Emits ContractUpgraded event before upgrade.
-    pub fn upgrade(env: Env, new_wasm_hash: [u8; 32]) -> Result<(), Error> {
-        let admin = get_admin(&env)?;
-        admin.require_auth();
-
-        let hash_bytes = soroban_sdk::BytesN::<32>::from_array(&env, &new_wasm_hash);
-
-        // Emit ContractUpgraded event
-        env.events().publish(
-            (
-                Symbol::new(&env, "Vaultix"),
-                Symbol::new(&env, "ContractUpgraded"),
-            ),
-            hash_bytes.clone(),
-        );
-
-        env.deployer().update_current_contract_wasm(hash_bytes);
-        Ok(())
-    }
-}
+mod archive;
+mod credits;
+mod events;
+mod governance;
+mod indexes;
+mod multisig;
+mod roles;
+mod screening;
+mod stats;
+mod storage;
+pub use archive::SettlementReceipt;
+use archive::{
+    escrow_exists, get_archived_milestones, is_terminal, record_settlement,
+    DEFAULT_RETENTION_PERIOD,
+};
+use credits::{add_credit, is_credit_mode};
+pub use events::EVENT_SCHEMA_VERSION;
+use events::*;
+use governance::submit_action;
+pub use governance::QueuedChange;
+use indexes::index_escrow;
+pub use indexes::EscrowPage;
+use multisig::ensure_multisig_disabled;
+pub use multisig::{AdminAction, MultisigConfig, MultisigProposal};
+use roles::require_role;
+use screening::ensure_screened;
+pub use screening::{ScreeningClient, ScreeningInterface, ScreeningSource};
+use stats::{record_fee_collected, record_payout, record_status_change, set_status};
+pub use stats::{StatusCounts, TokenStats};
+pub use storage::DataKey;
+use storage::{
+    get_legacy_escrow, has_legacy_config, migrate_legacy_keys, pending_fee_key,
+    remove_legacy_escrow,
+};
 
 #[contracttype]
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -39,7 +50,7 @@ pub enum MilestoneStatus {
 }
 
 #[contracttype]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Milestone {
     pub amount: i128,
     pub status: MilestoneStatus,
@@ -67,6 +78,77 @@ pub enum Resolution {
     Split,
 }
 
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FeePayer {
+    Recipient, // Fee deducted from the recipient's payout (or the depositor's refund)
+    Depositor, // Fee reserved on top of the principal at deposit time
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PendingFeeChange {
+    pub fee_bps: i128,
+    pub eta: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeeDiscount {
+    pub discount_bps: i128, // Basis points off the fee; BPS_DENOMINATOR exempts fully
+    pub valid_until: Option<u64>, // Discount lapses after this ledger timestamp
+}
+
+/// Amounts a settlement would move, as returned by the quote views.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SettlementQuote {
+    pub fee_bps: i128, // Effective fee rate applied
+    pub fee: i128,     // Fee accrued to the treasury
+    pub payout: i128,  // Amount paid to the recipient
+    pub refund: i128,  // Amount returned to the depositor
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PendingUpgrade {
+    pub wasm_hash: BytesN<32>,
+    pub eta: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MigrationProgress {
+    pub escrow_count: u32, // Escrows created under the previous layout
+    pub migrated: u32,     // Escrows moved to the current layout so far
+}
+
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+    Admin,
+    Operator,
+    Arbitrator,
+    Treasury,
+}
+
+/// Contract-wide configuration, stored as a single record.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Config {
+    pub admin: Address,
+    pub operator: Address,
+    pub arbitrator: Address,
+    pub treasury: Address,
+    pub fee_bps: i128,
+    pub fee_change_delay: u64,
+    pub governance_delay: u64, // Seconds queued configuration changes wait
+    pub emergency_exit_delay: u64, // Seconds an escrow stays blocked before depositors can exit
+    pub retention_period: u64, // Seconds settled escrows are kept before archival
+    pub credit_mode: bool,     // Payouts are credited rather than transferred
+    pub screening: ScreeningSource, // Where escrow parties are screened
+}
+
 #[contracttype]
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum ContractState {
@@ -74,9 +156,59 @@ pub enum ContractState {
     Paused,
 }
 
+/// Escrow amount bounds for an allowlisted token, inclusive on both ends.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TokenLimits {
+    pub min_amount: i128,
+    pub max_amount: i128,
+}
+
+/// Operations that can be paused independently of the global pause.
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PausableOperation {
+    Create,  // create_escrow, set_fee_payer
+    Deposit, // deposit_funds
+    Release, // release_milestone, confirm_delivery, complete_escrow
+    Dispute, // raise_dispute, resolve_dispute
+    Refund,  // cancel_escrow, refund_expired
+}
+
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct Escrow {
+    pub depositor: Address,
+    pub recipient: Address,
+    pub token_address: Address,
+    pub total_amount: i128,
+    pub total_released: i128,
+    pub milestone_count: u32, // Milestones are stored individually, see `get_milestone`
+    pub released_count: u32,
+    pub status: EscrowStatus,
+    pub deadline: u64,
+    pub resolution: Resolution,
+    pub fee_payer: FeePayer,
+    pub fee_reserve: i128,
+    pub fee_bps: i128, // Fee snapshot taken at creation
+}
+
+/// One escrow in a `create_escrows` batch; the depositor is shared by the batch.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct EscrowSpec {
+    pub escrow_id: u64,
+    pub recipient: Address,
+    pub token_address: Address,
+    pub milestones: Vec<Milestone>,
+    pub deadline: u64,
+}
+
+/// Escrow layout written by contract version 1, with milestones stored inline
+/// and fees resolved at settlement time.
+#[contracttype(export = false)]
+#[derive(Clone, Debug)]
+struct EscrowV1 {
     pub depositor: Address,
     pub recipient: Address,
     pub token_address: Address,
@@ -97,7 +229,7 @@ pub enum Error {
     MilestoneAlreadyReleased = 4,
     UnauthorizedAccess = 5,
     InvalidMilestoneAmount = 6,
-    TotalAmountMismatch = 7,
+    RoleNotDelegable = 7,
     InsufficientBalance = 8,
     EscrowNotActive = 9,
     VectorTooLarge = 10,
@@ -120,95 +252,194 @@ pub enum Error {
     Unauthorized = 27,
     OperatorNotInitialized = 28,
     ArbitratorNotInitialized = 29,
+    InsufficientAccruedFees = 30,
+    NoPendingFeeChange = 31,
+    TimelockNotReady = 32,
+    FeeDiscountNotFound = 33,
+    MigrationAlreadyApplied = 34,
+    NoPendingRoleTransfer = 35,
+    MultisigRequired = 36,
+    MultisigNotConfigured = 37,
+    NotASigner = 38,
+    InvalidMultisigConfig = 39,
+    ProposalNotFound = 40,
+    ProposalExpired = 41,
+    AlreadyApproved = 42,
+    ThresholdNotMet = 43,
+    ProposalAlreadyExecuted = 44,
+    EmergencyExitUnavailable = 45,
+    TokenNotAllowed = 46,
+    AmountOutOfRange = 47,
+    AddressBlocked = 48,
+    GovernanceDelayTooShort = 49,
+    NoCreditToWithdraw = 50,
 }
 
 const DEFAULT_FEE_BPS: i128 = 50;
 const BPS_DENOMINATOR: i128 = 10000;
+const DEFAULT_FEE_CHANGE_DELAY: u64 = 0;
+/// Time (in seconds) queued configuration changes wait before execution on
+/// deployments migrated from before the delay was chosen at `init`.
+const DEFAULT_GOVERNANCE_DELAY: u64 = 86_400;
+/// Shortest governance delay (in seconds) that can be configured, so queued
+/// changes always stay visible for long enough to be noticed and cancelled.
+const MIN_GOVERNANCE_DELAY: u64 = 3_600;
+/// Default time (in seconds) a pause must last before depositors can exit.
+const DEFAULT_EMERGENCY_EXIT_DELAY: u64 = 604_800;
+/// Longest emergency exit delay (in seconds), so a pause can never lock funds in
+/// indefinitely.
+const MAX_EMERGENCY_EXIT_DELAY: u64 = 2_592_000;
+
+/// Storage layout version produced by this code. Bump it whenever stored data
+/// changes shape and extend `migrate` to transform the previous layout.
+const CONTRACT_VERSION: u32 = 2;
+/// Maximum number of milestones a single escrow can hold. Only
+/// `MAX_MILESTONES_PER_CALL` fit in one call; `add_milestones` appends the rest.
+const MAX_MILESTONES: u32 = 200;
+/// Maximum number of milestones written, extended or removed by a single call,
+/// the limit escrows had before milestones were stored separately. Larger escrows
+/// are built and processed over several calls.
+const MAX_MILESTONES_PER_CALL: u32 = 20;
+/// Maximum number of escrows created by a single `create_escrows` call. The
+/// network's footprint limits are enforced when the transaction is simulated, so
+/// batches of escrows with many milestones may have to be smaller.
+const MAX_BATCH_SIZE: u32 = 25;
+/// Maximum number of tokens on the allowlist.
+const MAX_ALLOWED_TOKENS: u32 = 20;
+/// Ledgers closed in roughly one day at five seconds per ledger.
+const DAY_IN_LEDGERS: u32 = 17_280;
+/// Storage lifetime policy: every read or write of an entry extends its TTL to
+/// `*_TTL_EXTEND_TO` ledgers once fewer than `*_TTL_THRESHOLD` remain.
+const PERSISTENT_TTL_EXTEND_TO: u32 = 120 * DAY_IN_LEDGERS;
+const PERSISTENT_TTL_THRESHOLD: u32 = PERSISTENT_TTL_EXTEND_TO - 7 * DAY_IN_LEDGERS;
+const INSTANCE_TTL_EXTEND_TO: u32 = 120 * DAY_IN_LEDGERS;
+const INSTANCE_TTL_THRESHOLD: u32 = INSTANCE_TTL_EXTEND_TO - 7 * DAY_IN_LEDGERS;
+/// Minimum time (in seconds) between proposing and executing an upgrade.
+const UPGRADE_DELAY: u64 = 172_800;
 
 #[contract]
 pub struct VaultixEscrow;
 
 #[contractimpl]
 impl VaultixEscrow {
-    pub fn initialize(env: Env, treasury: Address, fee_bps: Option<i128>) -> Result<(), Error> {
-        treasury.require_auth();
+    /// Initialize the contract configuration in a single atomic step.
+    /// Can only be called once, and must be authorized by the admin.
+    ///
+    /// Afterwards each field has a dedicated, role-checked setter: roles move via
+    /// `propose_role_transfer`/`accept_role`, the platform fee via `update_fee`,
+    /// the fee change delay via `set_fee_change_delay`, and the governance delay
+    /// via `set_governance_delay`.
+    ///
+    /// # Arguments
+    /// * `env` - Soroban environment reference
+    /// * `admin` - Address allowed to manage roles, upgrades and delays
+    /// * `operator` - Address allowed to manage fees, discounts and pausing
+    /// * `arbitrator` - Address allowed to resolve disputes
+    /// * `treasury` - Address allowed to manage overrides and withdraw fees
+    /// * `fee_bps` - Global platform fee, defaulting to DEFAULT_FEE_BPS
+    /// * `governance_delay` - Seconds queued configuration changes wait before
+    ///   execution, at least `MIN_GOVERNANCE_DELAY`
+    ///
+    /// # Returns
+    /// Ok(()) on success, or Error if already initialized or validation fails
+    pub fn init(
+        env: Env,
+        admin: Address,
+        operator: Address,
+        arbitrator: Address,
+        treasury: Address,
+        fee_bps: Option<i128>,
+        governance_delay: u64,
+    ) -> Result<(), Error> {
+        // A deployment still on the version 1 layout is initialized but not yet migrated.
+        // Its escrows cannot be enumerated, but only its admin could have upgraded it,
+        // so its configuration keys are always present; `open_escrow` keeps their ids taken.
+        if get_stored_config(&env).is_some() || has_legacy_config(&env) {
+            return Err(Error::AlreadyInitialized);
+        }
 
-        let fee = fee_bps.unwrap_or(DEFAULT_FEE_BPS);
+        admin.require_auth();
 
+        let fee = fee_bps.unwrap_or(DEFAULT_FEE_BPS);
         if !(0..=BPS_DENOMINATOR).contains(&fee) {
             return Err(Error::InvalidFeeConfiguration);
         }
+        if governance_delay < MIN_GOVERNANCE_DELAY {
+            return Err(Error::GovernanceDelayTooShort);
+        }
 
+        let config = Config {
+            admin,
+            operator,
+            arbitrator,
+            treasury,
+            fee_bps: fee,
+            fee_change_delay: DEFAULT_FEE_CHANGE_DELAY,
+            governance_delay,
+            emergency_exit_delay: DEFAULT_EMERGENCY_EXIT_DELAY,
+            retention_period: DEFAULT_RETENTION_PERIOD,
+            credit_mode: false,
+            screening: ScreeningSource::Blocklist,
+        };
+        set_stored_config(&env, &config);
+        // Fresh deployments start on the current storage layout
         env.storage()
             .instance()
-            .set(&symbol_short!("treasury"), &treasury);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("fee_bps"), &fee);
-
-        let vaultix_topic = Symbol::new(&env, "Vaultix");
-
-        // Emit RoleUpdated(role, old_addr, new_addr) - using Option for old_addr
-        env.events().publish(
-            (
-                vaultix_topic.clone(),
-                Symbol::new(&env, "RoleUpdated"),
-                Symbol::new(&env, "Treasury"),
-            ),
-            (Option::<Address>::None, treasury.clone()),
+            .set(&DataKey::Version, &CONTRACT_VERSION);
+
+        for (role, addr) in [
+            (Role::Admin, &config.admin),
+            (Role::Operator, &config.operator),
+            (Role::Arbitrator, &config.arbitrator),
+            (Role::Treasury, &config.treasury),
+        ] {
+            publish_for(
+                &env,
+                role_symbol(&env, role),
+                RoleAssigned {
+                    holder: addr.clone(),
+                },
+            );
+        }
+        publish(
+            &env,
+            FeeUpdated {
+                old_fee_bps: 0,
+                new_fee_bps: fee,
+            },
         );
-
-        // Emit FeeUpdated(scope, key, old_fee, new_fee)
-        env.events().publish(
-            (vaultix_topic, Symbol::new(&env, "FeeUpdated")),
-            (
-                Symbol::new(&env, "Global"),
-                Symbol::new(&env, "PlatformFee"),
-                0i128,
-                fee,
-            ),
+        publish(
+            &env,
+            GovernanceDelayUpdated {
+                old_delay: 0,
+                new_delay: governance_delay,
+            },
         );
 
         Ok(())
     }
 
-    pub fn update_fee(env: Env, new_fee_bps: i128) -> Result<(), Error> {
-        let operator = get_operator(&env)?;
-        operator.require_auth();
-
-        if !(0..=BPS_DENOMINATOR).contains(&new_fee_bps) {
-            return Err(Error::InvalidFeeConfiguration);
-        }
-
-        let old_fee: i128 = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("fee_bps"))
-            .unwrap_or(DEFAULT_FEE_BPS);
-
-        env.storage()
-            .instance()
-            .set(&symbol_short!("fee_bps"), &new_fee_bps);
-
-        env.events().publish(
-            (
-                Symbol::new(&env, "Vaultix"),
-                Symbol::new(&env, "FeeUpdated"),
-            ),
-            (
-                Symbol::new(&env, "Global"),
-                Symbol::new(&env, "PlatformFee"),
-                old_fee,
-                new_fee_bps,
-            ),
-        );
-
-        Ok(())
+    /// Schedule a change to the global platform fee.
+    /// Only operators can call this function.
+    ///
+    /// The request passes through the governance queue. Once executed, the change
+    /// takes effect after the fee change delay has elapsed and
+    /// `apply_fee_change(None)` is called. With a zero fee change delay it applies
+    /// as soon as the queued change is executed.
+    pub fn update_fee(env: Env, caller: Address, new_fee_bps: i128) -> Result<(), Error> {
+        require_role(&env, Role::Operator, &caller)?;
+        ensure_multisig_disabled(&env)?;
+
+        submit_action(&env, AdminAction::UpdateFee(new_fee_bps), caller)
     }
 
-    /// Set fee override for a specific token.
+    /// Schedule a fee override for a specific token.
     /// Only treasury (admin) can call this function.
     ///
+    /// The change takes effect once the fee change delay has elapsed and
+    /// `apply_fee_change(Some(token_address))` is called. With a zero delay it
+    /// applies immediately.
+    ///
     /// # Arguments
     /// * `env` - Soroban environment reference
     /// * `token_address` - Address of the token to set fee for
@@ -217,42 +448,123 @@ impl VaultixEscrow {
     /// # Returns
     /// Ok(()) on success, or Error if validation fails
     pub fn set_token_fee(env: Env, token_address: Address, fee_bps: i128) -> Result<(), Error> {
-        let treasury: Address = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("treasury"))
-            .ok_or(Error::TreasuryNotInitialized)?;
+        let treasury = get_treasury(&env)?;
         treasury.require_auth();
+        ensure_multisig_disabled(&env)?;
 
-        if !(0..=BPS_DENOMINATOR).contains(&fee_bps) {
-            return Err(Error::InvalidFeeConfiguration);
+        submit_action(
+            &env,
+            AdminAction::SetTokenFee(token_address, fee_bps),
+            treasury,
+        )
+    }
+
+    /// Apply a scheduled fee change whose delay has elapsed.
+    /// Anyone can call this function.
+    ///
+    /// # Arguments
+    /// * `env` - Soroban environment reference
+    /// * `token_address` - Token whose override to apply, or `None` for the global fee
+    ///
+    /// # Returns
+    /// Ok(()) on success, or Error if nothing is pending or the delay has not elapsed
+    pub fn apply_fee_change(env: Env, token_address: Option<Address>) -> Result<(), Error> {
+        let pending_key = pending_fee_key(&token_address);
+        let pending: PendingFeeChange =
+            get_persistent(&env, &pending_key).ok_or(Error::NoPendingFeeChange)?;
+
+        if env.ledger().timestamp() < pending.eta {
+            return Err(Error::TimelockNotReady);
         }
 
-        let token_fee_key = get_token_fee_key(&token_address);
-        let old_fee: Option<i128> = env.storage().persistent().get(&token_fee_key);
-
-        env.storage().persistent().set(&token_fee_key, &fee_bps);
-
-        // Emit FeeUpdated event for token-level override
-        env.events().publish(
-            (
-                Symbol::new(&env, "Vaultix"),
-                Symbol::new(&env, "FeeUpdated"),
-            ),
-            (
-                Symbol::new(&env, "Token"),
-                token_address.clone(),
-                old_fee.unwrap_or(DEFAULT_FEE_BPS),
-                fee_bps,
-            ),
-        );
+        env.storage().persistent().remove(&pending_key);
+        apply_fee_value(&env, &token_address, pending.fee_bps);
 
         Ok(())
     }
 
+    /// Returns the scheduled fee change for a token, or for the global fee when `None`.
+    pub fn get_pending_fee_change(
+        env: Env,
+        token_address: Option<Address>,
+    ) -> Option<PendingFeeChange> {
+        get_persistent(&env, &pending_fee_key(&token_address))
+    }
+
+    /// Set the delay (in seconds) before scheduled global and token fee changes take effect.
+    /// Only admin can call this function. The new delay is queued and only
+    /// applies to fee changes scheduled after it executes.
+    pub fn set_fee_change_delay(env: Env, delay: u64) -> Result<(), Error> {
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+        ensure_multisig_disabled(&env)?;
+
+        submit_action(&env, AdminAction::SetFeeChangeDelay(delay), admin)
+    }
+
+    pub fn get_fee_change_delay(env: Env) -> u64 {
+        get_fee_change_delay(&env)
+    }
+
+    /// Grant a fee discount to an address.
+    /// Only operators can call this function. The discount is queued and only
+    /// takes effect once the queued change is executed.
+    ///
+    /// The discount applies to escrows created while it is active in which the
+    /// address is either the depositor or the recipient.
+    ///
+    /// # Arguments
+    /// * `env` - Soroban environment reference
+    /// * `addr` - Address receiving the discount
+    /// * `discount_bps` - Basis points off the fee (BPS_DENOMINATOR is a full exemption)
+    /// * `valid_until` - Optional ledger timestamp after which the discount lapses
+    ///
+    /// # Returns
+    /// Ok(()) on success, or Error if validation fails
+    pub fn add_fee_discount(
+        env: Env,
+        caller: Address,
+        addr: Address,
+        discount_bps: i128,
+        valid_until: Option<u64>,
+    ) -> Result<(), Error> {
+        require_role(&env, Role::Operator, &caller)?;
+        ensure_multisig_disabled(&env)?;
+
+        let discount = FeeDiscount {
+            discount_bps,
+            valid_until,
+        };
+        submit_action(&env, AdminAction::AddFeeDiscount(addr, discount), caller)
+    }
+
+    /// Remove a fee discount from an address.
+    /// Only operators can call this function. Removal is queued like granting,
+    /// so the discount keeps applying until the queued change executes.
+    pub fn remove_fee_discount(env: Env, caller: Address, addr: Address) -> Result<(), Error> {
+        require_role(&env, Role::Operator, &caller)?;
+        ensure_multisig_disabled(&env)?;
+
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::FeeDiscount(addr.clone()))
+        {
+            return Err(Error::FeeDiscountNotFound);
+        }
+        submit_action(&env, AdminAction::RemoveFeeDiscount(addr), caller)
+    }
+
+    pub fn get_fee_discount(env: Env, addr: Address) -> Option<FeeDiscount> {
+        get_persistent(&env, &DataKey::FeeDiscount(addr.clone()))
+    }
+
     /// Set fee override for a specific escrow.
     /// Only treasury (admin) can call this function.
     ///
+    /// The override is captured into the escrow's fee snapshot. Once an escrow is
+    /// funded its snapshot can only be lowered, never raised.
+    ///
     /// # Arguments
     /// * `env` - Soroban environment reference
     /// * `escrow_id` - ID of the escrow to set fee for
@@ -261,248 +573,647 @@ impl VaultixEscrow {
     /// # Returns
     /// Ok(()) on success, or Error if validation fails
     pub fn set_escrow_fee(env: Env, escrow_id: u64, fee_bps: i128) -> Result<(), Error> {
-        let treasury: Address = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("treasury"))
-            .ok_or(Error::TreasuryNotInitialized)?;
+        let treasury = get_treasury(&env)?;
         treasury.require_auth();
+        ensure_multisig_disabled(&env)?;
 
-        if !(0..=BPS_DENOMINATOR).contains(&fee_bps) {
-            return Err(Error::InvalidFeeConfiguration);
-        }
+        submit_action(
+            &env,
+            AdminAction::SetEscrowFee(escrow_id, fee_bps),
+            treasury,
+        )
+    }
 
-        let escrow_fee_key = get_escrow_fee_key(escrow_id);
-        let old_fee: Option<i128> = env.storage().persistent().get(&escrow_fee_key);
+    pub fn get_config(env: Env) -> Result<(Address, i128), Error> {
+        let config = get_stored_config(&env).ok_or(Error::TreasuryNotInitialized)?;
+        Ok((config.treasury, config.fee_bps))
+    }
 
-        env.storage().persistent().set(&escrow_fee_key, &fee_bps);
+    /// Returns the complete contract configuration.
+    pub fn get_full_config(env: Env) -> Result<Config, Error> {
+        get_stored_config(&env).ok_or(Error::AdminNotInitialized)
+    }
 
-        // Emit FeeUpdated event for escrow-level override
-        env.events().publish(
-            (
-                Symbol::new(&env, "Vaultix"),
-                Symbol::new(&env, "FeeUpdated"),
-            ),
-            (
-                Symbol::new(&env, "Escrow"),
-                escrow_id,
-                old_fee.unwrap_or(DEFAULT_FEE_BPS),
-                fee_bps,
-            ),
+    /// Withdraw accrued platform fees for a token.
+    /// Only treasury can call this function.
+    ///
+    /// Withdrawals are capped by the accrued fee balance, so they can never
+    /// reach into principal still held for escrows.
+    ///
+    /// # Arguments
+    /// * `env` - Soroban environment reference
+    /// * `token_address` - Token whose accrued fees are withdrawn
+    /// * `amount` - Amount to withdraw (must not exceed accrued fees)
+    /// * `to` - Destination of the withdrawn fees
+    ///
+    /// # Returns
+    /// Ok(()) on success, or Error if validation fails
+    pub fn withdraw_fees(
+        env: Env,
+        token_address: Address,
+        amount: i128,
+        to: Address,
+    ) -> Result<(), Error> {
+        let (treasury, _) = Self::get_config(env.clone())?;
+        treasury.require_auth();
+        ensure_migrated(&env)?;
+
+        if amount <= 0 {
+            return Err(Error::ZeroAmount);
+        }
+
+        let accrued_key = DataKey::AccruedFees(token_address.clone());
+        let accrued: i128 = get_persistent(&env, &accrued_key).unwrap_or(0);
+        if amount > accrued {
+            return Err(Error::InsufficientAccruedFees);
+        }
+
+        let remaining = accrued
+            .checked_sub(amount)
+            .ok_or(Error::InsufficientAccruedFees)?;
+        set_persistent(&env, &accrued_key, &remaining);
+
+        let token_client = token::Client::new(&env, &token_address);
+        safe_transfer(&token_client, &env.current_contract_address(), &to, amount)?;
+
+        publish_for(
+            &env,
+            token_address,
+            FeesWithdrawn {
+                to,
+                amount,
+                remaining,
+            },
         );
 
         Ok(())
     }
 
-    pub fn get_config(env: Env) -> Result<(Address, i128), Error> {
-        let treasury: Address = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("treasury"))
-            .ok_or(Error::TreasuryNotInitialized)?;
-        let fee_bps: i128 = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("fee_bps"))
-            .unwrap_or(DEFAULT_FEE_BPS);
-        Ok((treasury, fee_bps))
+    /// Returns the amount of `token_address` held on behalf of escrows: funded
+    /// amounts, including fee reserves, minus what has been paid out or accrued as fees.
+    /// Payouts credited in credit mode count until they are withdrawn.
+    pub fn get_liabilities(env: Env, token_address: Address) -> i128 {
+        get_liabilities(&env, &token_address)
     }
 
-    pub fn set_paused(env: Env, paused: bool) -> Result<(), Error> {
-        let operator = get_operator(&env)?;
-        operator.require_auth();
+    /// Move tokens sent to the contract by mistake to `to`. Only the balance above
+    /// escrow liabilities and accrued fees can be moved.
+    /// Only admin can call. The sweep is queued and the excess is measured when it
+    /// executes; `ExcessSwept` reports the amount moved.
+    pub fn sweep_excess(env: Env, token_address: Address, to: Address) -> Result<(), Error> {
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+        ensure_multisig_disabled(&env)?;
 
-        let state = if paused {
-            ContractState::Paused
-        } else {
-            ContractState::Active
-        };
-        env.storage()
-            .instance()
-            .set(&symbol_short!("state"), &state);
-
-        env.events().publish(
-            (
-                Symbol::new(&env, "Vaultix"),
-                Symbol::new(&env, "PausedStateChanged"),
-            ),
-            (paused, operator),
-        );
+        submit_action(&env, AdminAction::SweepExcess(token_address, to), admin)
+    }
 
-        Ok(())
+    /// Returns the platform fees accrued for a token and not yet withdrawn.
+    pub fn get_accrued_fees(env: Env, token_address: Address) -> i128 {
+        get_persistent(&env, &DataKey::AccruedFees(token_address.clone())).unwrap_or(0)
     }
 
-    pub fn init(
+    /// Accept `token_address` for new escrows, bounding each escrow's total amount.
+    /// Only operators can call. Calling again for a listed token updates its limits.
+    /// At most `MAX_ALLOWED_TOKENS` tokens can be listed at once. New listings and
+    /// limits are queued and apply once the queued change is executed.
+    pub fn allow_token(
         env: Env,
-        admin: Address,
-        operator: Address,
-        arbitrator: Address,
+        caller: Address,
+        token_address: Address,
+        min_amount: i128,
+        max_amount: i128,
     ) -> Result<(), Error> {
-        if env.storage().persistent().has(&admin_storage_key()) {
-            return Err(Error::AlreadyInitialized);
-        }
+        require_role(&env, Role::Operator, &caller)?;
+        ensure_multisig_disabled(&env)?;
 
-        admin.require_auth();
+        let limits = TokenLimits {
+            min_amount,
+            max_amount,
+        };
+        submit_action(&env, AdminAction::AllowToken(token_address, limits), caller)
+    }
 
-        env.storage().persistent().set(&admin_storage_key(), &admin);
-        env.storage()
-            .persistent()
-            .set(&Symbol::new(&env, "operator"), &operator);
-        env.storage()
-            .persistent()
-            .set(&Symbol::new(&env, "arbitrator"), &arbitrator);
+    /// Stop accepting `token_address` for new escrows. Existing escrows are unaffected.
+    /// Only operators can call. Like pausing, removal applies immediately.
+    pub fn remove_allowed_token(
+        env: Env,
+        caller: Address,
+        token_address: Address,
+    ) -> Result<(), Error> {
+        require_role(&env, Role::Operator, &caller)?;
+        ensure_multisig_disabled(&env)?;
 
-        let vaultix_topic = Symbol::new(&env, "Vaultix");
+        submit_action(&env, AdminAction::RemoveAllowedToken(token_address), caller)
+    }
 
-        env.events().publish(
-            (
-                vaultix_topic.clone(),
-                Symbol::new(&env, "RoleUpdated"),
-                Symbol::new(&env, "Admin"),
-            ),
-            (Option::<Address>::None, admin),
-        );
-        env.events().publish(
-            (
-                vaultix_topic.clone(),
-                Symbol::new(&env, "RoleUpdated"),
-                Symbol::new(&env, "Operator"),
-            ),
-            (Option::<Address>::None, operator),
-        );
-        env.events().publish(
-            (
-                vaultix_topic,
-                Symbol::new(&env, "RoleUpdated"),
-                Symbol::new(&env, "Arbitrator"),
-            ),
-            (Option::<Address>::None, arbitrator),
-        );
+    pub fn get_token_limits(env: Env, token_address: Address) -> Option<TokenLimits> {
+        get_persistent(&env, &DataKey::TokenLimits(token_address))
+    }
 
-        Ok(())
+    pub fn get_allowed_tokens(env: Env) -> Vec<Address> {
+        get_allowed_tokens(&env)
     }
 
-    pub fn create_escrow(
+    /// Pause or unpause all escrow operations.
+    /// Only operators can call. Pausing applies immediately; unpausing passes
+    /// through the governance queue.
+    pub fn set_paused(env: Env, caller: Address, paused: bool) -> Result<(), Error> {
+        require_role(&env, Role::Operator, &caller)?;
+        ensure_multisig_disabled(&env)?;
+
+        submit_action(&env, AdminAction::SetPaused(paused), caller)
+    }
+
+    /// Pause or unpause a single class of operations for all escrows.
+    /// Only operators can call. Unpausing passes through the governance queue.
+    pub fn set_operation_paused(
         env: Env,
-        escrow_id: u64,
-        depositor: Address,
-        recipient: Address,
+        caller: Address,
+        operation: PausableOperation,
+        paused: bool,
+    ) -> Result<(), Error> {
+        require_role(&env, Role::Operator, &caller)?;
+        ensure_multisig_disabled(&env)?;
+
+        submit_action(
+            &env,
+            AdminAction::SetOperationPaused(operation, paused),
+            caller,
+        )
+    }
+
+    /// Pause or unpause every operation on escrows denominated in `token_address`.
+    /// Only operators can call. Unpausing passes through the governance queue.
+    pub fn set_token_paused(
+        env: Env,
+        caller: Address,
         token_address: Address,
-        milestones: Vec<Milestone>,
-        deadline: u64,
+        paused: bool,
     ) -> Result<(), Error> {
-        depositor.require_auth();
-        ensure_not_paused(&env)?;
+        require_role(&env, Role::Operator, &caller)?;
+        ensure_multisig_disabled(&env)?;
 
-        if depositor == recipient {
-            return Err(Error::SelfDealing);
-        }
+        submit_action(
+            &env,
+            AdminAction::SetTokenPaused(token_address, paused),
+            caller,
+        )
+    }
 
-        let storage_key = get_storage_key(escrow_id);
-        if env.storage().persistent().has(&storage_key) {
-            return Err(Error::EscrowAlreadyExists);
-        }
+    /// Whether `operation` is currently blocked, either globally, for the
+    /// operation, or (when given) for `token_address`.
+    pub fn is_paused(
+        env: Env,
+        operation: PausableOperation,
+        token_address: Option<Address>,
+    ) -> bool {
+        get_paused_since(&env, operation, token_address.as_ref()).is_some()
+    }
 
-        let total_amount = validate_milestones(&milestones)?;
+    /// Set how long pauses must block an escrow before `emergency_withdraw` opens.
+    /// Only admin can call. A new delay is queued, so depositors can see it
+    /// coming before it changes when their exit opens. Delays above
+    /// `MAX_EMERGENCY_EXIT_DELAY` are clamped to it.
+    pub fn set_emergency_exit_delay(env: Env, delay: u64) -> Result<(), Error> {
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+        ensure_multisig_disabled(&env)?;
 
-        let mut initialized_milestones = Vec::new(&env);
-        for milestone in milestones.iter() {
-            let mut m = milestone.clone();
-            m.status = MilestoneStatus::Pending;
-            initialized_milestones.push_back(m);
-        }
+        submit_action(&env, AdminAction::SetEmergencyExitDelay(delay), admin)
+    }
 
-        let escrow = Escrow {
-            depositor: depositor.clone(),
-            recipient: recipient.clone(),
-            token_address: token_address.clone(),
-            total_amount,
-            total_released: 0,
-            milestones: initialized_milestones,
-            status: EscrowStatus::Created,
-            deadline,
-            resolution: Resolution::None,
-        };
+    pub fn get_emergency_exit_delay(env: Env) -> u64 {
+        get_emergency_exit_delay(&env)
+    }
 
-        env.storage().persistent().set(&storage_key, &escrow);
-        env.storage()
-            .persistent()
-            .extend_ttl(&storage_key, 100, 2_000_000);
+    /// Propose transferring `role` to `new_addr`.
+    /// Only admin can call, so a compromised operator, arbitrator or treasury key
+    /// cannot block its own rotation. The transfer completes when `new_addr`
+    /// calls `accept_role`; proposing again replaces any pending transfer.
+    /// The proposal itself passes through the governance queue.
+    pub fn propose_role_transfer(env: Env, role: Role, new_addr: Address) -> Result<(), Error> {
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+        ensure_multisig_disabled(&env)?;
+
+        get_role_holder(&env, role)?;
+        submit_action(
+            &env,
+            AdminAction::ProposeRoleTransfer(role, new_addr),
+            admin,
+        )
+    }
 
-        // Standardized Event
-        env.events().publish(
-            (
-                Symbol::new(&env, "Vaultix"),
-                Symbol::new(&env, "EscrowCreated"),
-                escrow_id,
-            ),
-            (depositor, recipient, token_address, total_amount, deadline),
+    /// Accept a pending transfer of `role`. Must be authorized by the proposed address.
+    pub fn accept_role(env: Env, role: Role) -> Result<(), Error> {
+        let pending_key = DataKey::PendingRoleTransfer(role);
+        let new_addr: Address =
+            get_persistent(&env, &pending_key).ok_or(Error::NoPendingRoleTransfer)?;
+        new_addr.require_auth();
+
+        let old_addr = get_role_holder(&env, role)?;
+        set_role_holder(&env, role, &new_addr)?;
+        env.storage().persistent().remove(&pending_key);
+
+        publish_for(
+            &env,
+            role_symbol(&env, role),
+            RoleUpdated {
+                previous: old_addr,
+                holder: new_addr,
+            },
         );
 
         Ok(())
     }
 
-    pub fn deposit_funds(env: Env, escrow_id: u64) -> Result<(), Error> {
-        let storage_key = get_storage_key(escrow_id);
-        ensure_not_paused(&env)?;
+    /// Cancel a pending transfer of `role`. Only admin can call.
+    pub fn cancel_role_transfer(env: Env, role: Role) -> Result<(), Error> {
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+        ensure_multisig_disabled(&env)?;
 
-        let mut escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&storage_key)
-            .ok_or(Error::EscrowNotFound)?;
+        submit_action(&env, AdminAction::CancelRoleTransfer(role), admin)
+    }
+
+    pub fn get_pending_role_transfer(env: Env, role: Role) -> Option<Address> {
+        get_persistent(&env, &DataKey::PendingRoleTransfer(role))
+    }
+
+    pub fn get_role(env: Env, role: Role) -> Result<Address, Error> {
+        get_role_holder(&env, role)
+    }
+
+    /// Propose a contract upgrade to `new_wasm_hash`.
+    /// Only admin can call. The proposal is queued; once executed, the upgrade can
+    /// be executed after `UPGRADE_DELAY` seconds. Proposing again replaces any
+    /// pending proposal.
+    /// WARNING: Upgrades MUST either preserve storage layout (structs, enums, keys) or bump
+    /// `CONTRACT_VERSION` and extend `migrate` to transform the previous layout.
+    pub fn propose_upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+        ensure_multisig_disabled(&env)?;
+
+        submit_action(&env, AdminAction::ProposeUpgrade(new_wasm_hash), admin)
+    }
+
+    /// Cancel the pending upgrade proposal. Only admin can call.
+    pub fn cancel_upgrade(env: Env) -> Result<(), Error> {
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+        ensure_multisig_disabled(&env)?;
+
+        unschedule_upgrade(&env)
+    }
+
+    /// Execute the pending upgrade once its delay has elapsed.
+    /// Only admin can call. Emits ContractUpgraded before swapping the contract code;
+    /// `migrate` must be called afterwards if the new code bumps the storage version.
+    pub fn execute_upgrade(env: Env) -> Result<(), Error> {
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+        ensure_multisig_disabled(&env)?;
+
+        perform_upgrade(&env)
+    }
+
+    pub fn get_pending_upgrade(env: Env) -> Option<PendingUpgrade> {
+        env.storage().instance().get(&DataKey::PendingUpgrade)
+    }
+
+    /// Returns the storage layout version the contract data has been migrated to.
+    pub fn get_version(env: Env) -> u32 {
+        get_contract_version(&env)
+    }
+
+    /// Migrate stored data to the layout of the running code (`CONTRACT_VERSION`).
+    /// Only admin can call.
+    ///
+    /// Contract storage cannot be enumerated, so the caller supplies
+    /// `escrow_count`, the number of escrows created under the previous layout
+    /// (e.g. counted from `EscrowCreated` events), and lists their ids over as
+    /// many calls as needed. Ids that are unknown or already migrated are
+    /// skipped, and later calls may correct `escrow_count`. `token_addresses`
+    /// lists tokens with a fee override that no listed escrow uses.
+    ///
+    /// Each call lists at most `MAX_BATCH_SIZE` escrows and tokens. Batches that
+    /// exceed the network's footprint limits fail in simulation and can be split.
+    ///
+    /// Escrow operations and fund movements are refused until `escrow_count`
+    /// escrows have been migrated, at which point the version is bumped. A
+    /// version 1 escrow left out because `escrow_count` was too low is migrated
+    /// the first time it is loaded, e.g. by its parties or by `bump_escrow`.
+    ///
+    /// # Returns
+    /// The number of escrows migrated by this call
+    pub fn migrate(
+        env: Env,
+        escrow_count: u32,
+        escrow_ids: Vec<u64>,
+        token_addresses: Vec<Address>,
+    ) -> Result<u32, Error> {
+        let from_version = get_contract_version(&env);
+        if from_version >= CONTRACT_VERSION {
+            return Err(Error::MigrationAlreadyApplied);
+        }
+        if escrow_ids.len() > MAX_BATCH_SIZE || token_addresses.len() > MAX_BATCH_SIZE {
+            return Err(Error::VectorTooLarge);
+        }
+
+        // Version 1 kept roles in separate keys; authorize before touching storage
+        let legacy_config = match get_stored_config(&env) {
+            Some(_) => None,
+            None => Some(load_legacy_config(&env)?),
+        };
+        let admin = match &legacy_config {
+            Some(config) => config.admin.clone(),
+            None => get_admin(&env)?,
+        };
+        admin.require_auth();
+
+        if let Some(config) = legacy_config {
+            set_stored_config(&env, &config);
+            remove_legacy_config(&env);
+        }
+        migrate_legacy_keys(&env, &token_addresses);
+
+        let mut migrated = 0u32;
+        for escrow_id in escrow_ids.iter() {
+            if migrate_escrow_v1(&env, escrow_id)? {
+                migrated += 1;
+            }
+        }
+
+        let mut progress = get_migration_progress(&env).unwrap_or(MigrationProgress {
+            escrow_count,
+            migrated: 0,
+        });
+        progress.escrow_count = escrow_count;
+        progress.migrated += migrated;
+
+        if progress.migrated < progress.escrow_count {
+            env.storage().instance().set(&DataKey::Migration, &progress);
+            return Ok(migrated);
+        }
+
+        env.storage().instance().remove(&DataKey::Migration);
+        env.storage()
+            .instance()
+            .set(&DataKey::Version, &CONTRACT_VERSION);
+
+        publish(
+            &env,
+            ContractMigrated {
+                from_version,
+                to_version: CONTRACT_VERSION,
+                migrated: progress.migrated,
+            },
+        );
+
+        Ok(migrated)
+    }
+
+    /// Returns how far a migration in batches has got, while one is in progress.
+    pub fn get_migration_progress(env: Env) -> Option<MigrationProgress> {
+        get_migration_progress(&env)
+    }
+
+    pub fn create_escrow(
+        env: Env,
+        escrow_id: u64,
+        depositor: Address,
+        recipient: Address,
+        token_address: Address,
+        milestones: Vec<Milestone>,
+        deadline: u64,
+    ) -> Result<(), Error> {
+        depositor.require_auth();
+        open_escrow(
+            &env,
+            escrow_id,
+            &depositor,
+            &recipient,
+            &token_address,
+            &milestones,
+            deadline,
+        )?;
+        Ok(())
+    }
+
+    /// Append milestones to an escrow that has not been funded yet.
+    /// Only the depositor can call. Each call adds at most `MAX_MILESTONES_PER_CALL`
+    /// milestones, so escrows of up to `MAX_MILESTONES` are built over several calls.
+    pub fn add_milestones(
+        env: Env,
+        escrow_id: u64,
+        milestones: Vec<Milestone>,
+    ) -> Result<(), Error> {
+        let mut escrow = load_escrow(&env, escrow_id)?;
+        ensure_not_paused(&env, PausableOperation::Create, &escrow.token_address)?;
         escrow.depositor.require_auth();
 
         if escrow.status != EscrowStatus::Created {
             return Err(Error::EscrowAlreadyFunded);
         }
 
-        let token_client = token::Client::new(&env, &escrow.token_address);
-        // Defensive checks to avoid host traps when the token contract would trap
-        // on transfer_from due to missing allowance or insufficient balance.
-        // Check depositor balance first.
-        let depositor_balance = token_client.balance(&escrow.depositor);
-        if depositor_balance < escrow.total_amount {
-            return Err(Error::InsufficientBalance);
+        let added_amount = validate_milestones(&milestones)?;
+        let milestone_count = escrow
+            .milestone_count
+            .checked_add(milestones.len())
+            .filter(|count| *count <= MAX_MILESTONES)
+            .ok_or(Error::VectorTooLarge)?;
+        let total_amount = escrow
+            .total_amount
+            .checked_add(added_amount)
+            .ok_or(Error::InvalidMilestoneAmount)?;
+        ensure_token_allowed(&env, &escrow.token_address, total_amount)?;
+
+        save_new_milestones(&env, escrow_id, escrow.milestone_count, &milestones);
+        escrow.milestone_count = milestone_count;
+        escrow.total_amount = total_amount;
+        save_escrow(&env, escrow_id, &escrow);
+
+        publish_for(
+            &env,
+            escrow_id,
+            MilestonesAdded {
+                count: milestones.len(),
+                total_amount,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Creates several escrows for one depositor and returns their IDs in order.
+    ///
+    /// Each spec is validated as by `create_escrow`; if any fails, none are created.
+    /// With `fund` set, the escrows are funded immediately with one transfer per
+    /// token covering the batch, so the depositor approves the combined amount.
+    /// A batch holds at most `MAX_BATCH_SIZE` escrows.
+    pub fn create_escrows(
+        env: Env,
+        depositor: Address,
+        specs: Vec<EscrowSpec>,
+        fund: bool,
+    ) -> Result<Vec<u64>, Error> {
+        depositor.require_auth();
+        if specs.len() > MAX_BATCH_SIZE {
+            return Err(Error::VectorTooLarge);
         }
 
-        // Check allowance granted to this contract (spender) by the depositor.
-        // If allowance is insufficient, return a TokenTransferFailed error instead
-        // of invoking transfer_from which would trap the host.
-        let spender = env.current_contract_address();
-        let allowance = token_client.allowance(&escrow.depositor, &spender);
-        if allowance < escrow.total_amount {
-            return Err(Error::TokenTransferFailed);
+        let mut escrow_ids = Vec::new(&env);
+        let mut funding: Map<Address, i128> = Map::new(&env);
+        for spec in specs.iter() {
+            let escrow = open_escrow(
+                &env,
+                spec.escrow_id,
+                &depositor,
+                &spec.recipient,
+                &spec.token_address,
+                &spec.milestones,
+                spec.deadline,
+            )?;
+            escrow_ids.push_back(spec.escrow_id);
+            if fund {
+                let total = funding
+                    .get(escrow.token_address.clone())
+                    .unwrap_or(0)
+                    .checked_add(escrow.total_amount)
+                    .ok_or(Error::InvalidMilestoneAmount)?;
+                funding.set(escrow.token_address, total);
+            }
         }
 
-        // Safe to call transfer_from now that basic preconditions hold.
-        token_client.transfer_from(&spender, &escrow.depositor, &spender, &escrow.total_amount);
+        if fund {
+            ensure_screened(&env, &depositor)?;
+            for (token_address, amount) in funding.iter() {
+                ensure_not_paused(&env, PausableOperation::Deposit, &token_address)?;
+                pull_funds(&env, &token_address, &depositor, amount)?;
+            }
+            // New escrows default to recipient-pays, so no fee reserve is pulled
+            for escrow_id in escrow_ids.iter() {
+                let mut escrow = load_escrow(&env, escrow_id)?;
+                mark_funded(&env, escrow_id, &mut escrow, 0);
+            }
+        }
 
-        escrow.status = EscrowStatus::Active;
-        env.storage().persistent().set(&storage_key, &escrow);
-        env.storage()
-            .persistent()
-            .extend_ttl(&storage_key, 100, 2_000_000);
+        Ok(escrow_ids)
+    }
 
-        // Standardized Event
-        env.events().publish(
-            (
-                Symbol::new(&env, "Vaultix"),
-                Symbol::new(&env, "EscrowFunded"),
-                escrow_id,
-            ),
-            escrow.total_amount,
-        );
+    /// Choose who pays the platform fee for an escrow.
+    /// Only the depositor can call this, and only before the escrow is funded.
+    ///
+    /// In `FeePayer::Depositor` mode, `deposit_funds` pulls the projected fee on
+    /// top of the principal and releases pay the recipient the full milestone
+    /// amount. Any unused fee reserve is returned to the depositor on settlement.
+    pub fn set_fee_payer(env: Env, escrow_id: u64, fee_payer: FeePayer) -> Result<(), Error> {
+        let mut escrow = load_escrow(&env, escrow_id)?;
+        ensure_not_paused(&env, PausableOperation::Create, &escrow.token_address)?;
+        escrow.depositor.require_auth();
+
+        if escrow.status != EscrowStatus::Created {
+            return Err(Error::EscrowAlreadyFunded);
+        }
+
+        escrow.fee_payer = fee_payer;
+        save_escrow(&env, escrow_id, &escrow);
+
+        publish_for(&env, escrow_id, FeePayerUpdated { fee_payer });
+
+        Ok(())
+    }
+
+    pub fn deposit_funds(env: Env, escrow_id: u64) -> Result<(), Error> {
+        let mut escrow = load_escrow(&env, escrow_id)?;
+        ensure_not_paused(&env, PausableOperation::Deposit, &escrow.token_address)?;
+        escrow.depositor.require_auth();
+
+        if escrow.status != EscrowStatus::Created {
+            return Err(Error::EscrowAlreadyFunded);
+        }
+        ensure_screened(&env, &escrow.depositor)?;
+
+        // In depositor-pays mode the projected fee is reserved on top of the principal
+        let fee_reserve = match escrow.fee_payer {
+            FeePayer::Recipient => 0,
+            FeePayer::Depositor => calculate_fee(escrow.total_amount, escrow.fee_bps)?,
+        };
+        let funding_amount = escrow
+            .total_amount
+            .checked_add(fee_reserve)
+            .ok_or(Error::InvalidMilestoneAmount)?;
+
+        pull_funds(
+            &env,
+            &escrow.token_address,
+            &escrow.depositor,
+            funding_amount,
+        )?;
+        mark_funded(&env, escrow_id, &mut escrow, fee_reserve);
 
         Ok(())
     }
 
     pub fn get_escrow(env: Env, escrow_id: u64) -> Result<Escrow, Error> {
-        let storage_key = get_storage_key(escrow_id);
-        env.storage()
-            .persistent()
-            .get(&storage_key)
-            .ok_or(Error::EscrowNotFound)
+        load_escrow(&env, escrow_id)
+    }
+
+    /// Returns a milestone with its status as seen by the escrow's current state,
+    /// so unreleased milestones read as disputed once a dispute is raised.
+    pub fn get_milestone(
+        env: Env,
+        escrow_id: u64,
+        milestone_index: u32,
+    ) -> Result<Milestone, Error> {
+        let escrow = load_escrow(&env, escrow_id)?;
+        load_milestone_view(&env, &escrow, escrow_id, milestone_index)
+    }
+
+    /// Returns up to `limit` milestones starting at `start`, in index order.
+    /// At most `MAX_MILESTONES_PER_CALL` are returned per call.
+    pub fn get_milestones(
+        env: Env,
+        escrow_id: u64,
+        start: u32,
+        limit: u32,
+    ) -> Result<Vec<Milestone>, Error> {
+        let escrow = load_escrow(&env, escrow_id)?;
+        let end = start
+            .saturating_add(limit.min(MAX_MILESTONES_PER_CALL))
+            .min(escrow.milestone_count);
+        let mut milestones = Vec::new(&env);
+        for index in start..end {
+            milestones.push_back(load_milestone_view(&env, &escrow, escrow_id, index)?);
+        }
+        Ok(milestones)
+    }
+
+    /// Extend the storage lifetime of an escrow, its milestones from `start` on
+    /// and the contract instance.
+    /// Anyone can call this function, so long-running escrows can be kept alive
+    /// without waiting for the parties to act. At most `MAX_MILESTONES_PER_CALL`
+    /// milestones are extended per call; the index to continue from is returned
+    /// while milestones remain.
+    pub fn bump_escrow(env: Env, escrow_id: u64, start: u32) -> Result<Option<u32>, Error> {
+        let escrow = load_escrow(&env, escrow_id)?;
+        // Milestones already removed by `archive_escrow` have nothing to extend
+        let start = start.max(get_archived_milestones(&env, escrow_id));
+        let end = start
+            .saturating_add(MAX_MILESTONES_PER_CALL)
+            .min(escrow.milestone_count);
+        for index in start..end {
+            extend_persistent_ttl(&env, &DataKey::Milestone(escrow_id, index));
+        }
+
+        let escrow_fee_key = DataKey::EscrowFee(escrow_id);
+        if env.storage().persistent().has(&escrow_fee_key) {
+            extend_persistent_ttl(&env, &escrow_fee_key);
+        }
+
+        Ok((end < escrow.milestone_count).then_some(end))
     }
 
     pub fn get_state(env: Env, escrow_id: u64) -> Result<EscrowStatus, Error> {
@@ -511,97 +1222,170 @@ impl VaultixEscrow {
     }
 
     pub fn release_milestone(env: Env, escrow_id: u64, milestone_index: u32) -> Result<(), Error> {
-        let storage_key = get_storage_key(escrow_id);
-        ensure_not_paused(&env)?;
-
-        let mut escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&storage_key)
-            .ok_or(Error::EscrowNotFound)?;
+        let mut escrow = load_escrow(&env, escrow_id)?;
+        ensure_not_paused(&env, PausableOperation::Release, &escrow.token_address)?;
         escrow.depositor.require_auth();
 
-        if escrow.status != EscrowStatus::Active {
-            return Err(Error::EscrowNotActive);
-        }
-        if milestone_index >= escrow.milestones.len() {
-            return Err(Error::MilestoneNotFound);
-        }
-
-        let mut milestone = escrow
-            .milestones
-            .get(milestone_index)
-            .ok_or(Error::MilestoneNotFound)?;
-        if milestone.status == MilestoneStatus::Released {
-            return Err(Error::MilestoneAlreadyReleased);
-        }
+        let mut milestone = get_releasable_milestone(&env, &escrow, escrow_id, milestone_index)?;
 
-        let (treasury, _) = Self::get_config(env.clone())?;
-        let fee_bps = resolve_fee(&env, escrow_id, &escrow.token_address)?;
-        let fee = calculate_fee(milestone.amount, fee_bps)?;
-        let payout = milestone
-            .amount
-            .checked_sub(fee)
-            .ok_or(Error::InvalidMilestoneAmount)?;
+        // Fees accrue on behalf of the treasury, so it must be configured.
+        Self::get_config(env.clone())?;
+        let SettlementQuote { payout, fee, .. } = compute_release(&mut escrow, milestone.amount)?;
 
-        let token_client = token::Client::new(&env, &escrow.token_address);
-        safe_transfer(
-            &token_client,
-            &env.current_contract_address(),
-            &escrow.recipient,
-            payout,
-        )?;
+        pay_out(&env, &escrow, &escrow.recipient, payout)?;
 
-        if fee > 0 {
-            safe_transfer(
-                &token_client,
-                &env.current_contract_address(),
-                &treasury,
-                fee,
-            )?;
-        }
+        accrue_fee(&env, &escrow.token_address, fee)?;
 
         milestone.status = MilestoneStatus::Released;
-        escrow.milestones.set(milestone_index, milestone.clone());
+        save_milestone(&env, escrow_id, milestone_index, &milestone);
 
+        escrow.released_count += 1;
         escrow.total_released = escrow
             .total_released
             .checked_add(milestone.amount)
             .ok_or(Error::InvalidMilestoneAmount)?;
 
-        env.storage().persistent().set(&storage_key, &escrow);
-        env.storage()
-            .persistent()
-            .extend_ttl(&storage_key, 100, 2_000_000);
+        save_escrow(&env, escrow_id, &escrow);
 
         // Standardized Event
-        env.events().publish(
-            (
-                Symbol::new(&env, "Vaultix"),
-                Symbol::new(&env, "MilestoneReleased"),
-                escrow_id,
+        publish_for(
+            &env,
+            escrow_id,
+            MilestoneReleased {
                 milestone_index,
-            ),
-            (payout, fee),
+                payout,
+                fee,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Release several milestones of an escrow in one call.
+    /// Only the depositor can call this. Either every listed milestone is released
+    /// or none is; fees are computed per milestone exactly as in `release_milestone`,
+    /// and the recipient receives the combined payout in a single transfer.
+    pub fn release_milestones(env: Env, escrow_id: u64, indices: Vec<u32>) -> Result<(), Error> {
+        let mut escrow = load_escrow(&env, escrow_id)?;
+        ensure_not_paused(&env, PausableOperation::Release, &escrow.token_address)?;
+        escrow.depositor.require_auth();
+
+        if indices.is_empty() {
+            return Err(Error::MilestoneNotFound);
+        }
+        if indices.len() > escrow.milestone_count || indices.len() > MAX_MILESTONES_PER_CALL {
+            return Err(Error::VectorTooLarge);
+        }
+
+        // Fees accrue on behalf of the treasury, so it must be configured.
+        Self::get_config(env.clone())?;
+
+        let mut released = Vec::new(&env);
+        let mut total_payout: i128 = 0;
+        let mut total_fee: i128 = 0;
+        for (position, milestone_index) in indices.iter().enumerate() {
+            if indices.first_index_of(milestone_index) != Some(position as u32) {
+                return Err(Error::MilestoneAlreadyReleased);
+            }
+            let mut milestone =
+                get_releasable_milestone(&env, &escrow, escrow_id, milestone_index)?;
+            let SettlementQuote { payout, fee, .. } =
+                compute_release(&mut escrow, milestone.amount)?;
+            total_payout = total_payout
+                .checked_add(payout)
+                .ok_or(Error::InvalidMilestoneAmount)?;
+            total_fee = total_fee
+                .checked_add(fee)
+                .ok_or(Error::InvalidMilestoneAmount)?;
+
+            milestone.status = MilestoneStatus::Released;
+            save_milestone(&env, escrow_id, milestone_index, &milestone);
+            escrow.released_count += 1;
+            escrow.total_released = escrow
+                .total_released
+                .checked_add(milestone.amount)
+                .ok_or(Error::InvalidMilestoneAmount)?;
+            released.push_back((milestone_index, payout, fee));
+        }
+
+        pay_out(&env, &escrow, &escrow.recipient, total_payout)?;
+        accrue_fee(&env, &escrow.token_address, total_fee)?;
+        save_escrow(&env, escrow_id, &escrow);
+
+        for (milestone_index, payout, fee) in released.iter() {
+            publish_for(
+                &env,
+                escrow_id,
+                MilestoneReleased {
+                    milestone_index,
+                    payout,
+                    fee,
+                },
+            );
+        }
+        publish_for(
+            &env,
+            escrow_id,
+            MilestonesReleased {
+                count: indices.len(),
+                total_payout,
+                total_fee,
+            },
         );
 
         Ok(())
     }
 
+    /// Quote the fee and payout for releasing a milestone without changing state.
+    /// Uses the same fee computation as `release_milestone`.
+    pub fn quote_release(
+        env: Env,
+        escrow_id: u64,
+        milestone_index: u32,
+    ) -> Result<SettlementQuote, Error> {
+        let mut escrow = Self::get_escrow(env.clone(), escrow_id)?;
+        let milestone = get_releasable_milestone(&env, &escrow, escrow_id, milestone_index)?;
+        Self::get_config(env)?;
+        compute_release(&mut escrow, milestone.amount)
+    }
+
+    /// Quote the fee and depositor refund for cancelling an escrow without changing state.
+    /// Uses the same fee computation as `cancel_escrow`.
+    pub fn quote_cancel(env: Env, escrow_id: u64) -> Result<SettlementQuote, Error> {
+        let mut escrow = Self::get_escrow(env.clone(), escrow_id)?;
+        ensure_cancellable(&escrow)?;
+        if escrow.status != EscrowStatus::Active {
+            // Unfunded escrows are cancelled without any transfers
+            return Ok(SettlementQuote {
+                fee_bps: 0,
+                fee: 0,
+                payout: 0,
+                refund: 0,
+            });
+        }
+        compute_cancel(&env, &mut escrow)
+    }
+
+    /// Quote the fee and depositor refund for an expiry refund without changing state.
+    /// Uses the same fee computation as `refund_expired`; the deadline is not checked.
+    pub fn quote_refund(env: Env, escrow_id: u64) -> Result<SettlementQuote, Error> {
+        let mut escrow = Self::get_escrow(env.clone(), escrow_id)?;
+        if escrow.status != EscrowStatus::Active {
+            return Err(Error::InvalidStatusForRefund);
+        }
+        let remaining_balance = get_refundable_balance(&escrow)?;
+        Self::get_config(env)?;
+        compute_refund(&mut escrow, remaining_balance, true)
+    }
+
     pub fn confirm_delivery(
         env: Env,
         escrow_id: u64,
         milestone_index: u32,
         buyer: Address,
     ) -> Result<(), Error> {
-        let storage_key = get_storage_key(escrow_id);
-        ensure_not_paused(&env)?;
-
-        let mut escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&storage_key)
-            .ok_or(Error::EscrowNotFound)?;
+        let mut escrow = load_escrow(&env, escrow_id)?;
+        ensure_not_paused(&env, PausableOperation::Release, &escrow.token_address)?;
         buyer.require_auth();
 
         if escrow.depositor != buyer {
@@ -610,59 +1394,41 @@ impl VaultixEscrow {
         if escrow.status != EscrowStatus::Active {
             return Err(Error::EscrowNotActive);
         }
-        if milestone_index >= escrow.milestones.len() {
-            return Err(Error::MilestoneNotFound);
-        }
-
-        let mut milestone = escrow
-            .milestones
-            .get(milestone_index)
-            .ok_or(Error::MilestoneNotFound)?;
+        let mut milestone = load_milestone(&env, &escrow, escrow_id, milestone_index)?;
         if milestone.status == MilestoneStatus::Released {
             return Err(Error::MilestoneAlreadyReleased);
         }
 
         milestone.status = MilestoneStatus::Released;
-        escrow.milestones.set(milestone_index, milestone.clone());
+        save_milestone(&env, escrow_id, milestone_index, &milestone);
 
+        escrow.released_count += 1;
         escrow.total_released = escrow
             .total_released
             .checked_add(milestone.amount)
             .ok_or(Error::InvalidMilestoneAmount)?;
 
-        let token_client = token::Client::new(&env, &escrow.token_address);
-        safe_transfer(
-            &token_client,
-            &env.current_contract_address(),
-            &escrow.recipient,
-            milestone.amount,
-        )?;
+        pay_out(&env, &escrow, &escrow.recipient, milestone.amount)?;
 
-        env.storage().persistent().set(&storage_key, &escrow);
+        save_escrow(&env, escrow_id, &escrow);
 
         // Standardized Event
-        env.events().publish(
-            (
-                Symbol::new(&env, "Vaultix"),
-                Symbol::new(&env, "MilestoneReleased"),
-                escrow_id,
+        publish_for(
+            &env,
+            escrow_id,
+            MilestoneReleased {
                 milestone_index,
-            ),
-            (milestone.amount, 0i128),
+                payout: milestone.amount,
+                fee: 0,
+            },
         );
 
         Ok(())
     }
 
     pub fn raise_dispute(env: Env, escrow_id: u64, caller: Address) -> Result<(), Error> {
-        let storage_key = get_storage_key(escrow_id);
-        ensure_not_paused(&env)?;
-
-        let mut escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&storage_key)
-            .ok_or(Error::EscrowNotFound)?;
+        let mut escrow = load_escrow(&env, escrow_id)?;
+        ensure_not_paused(&env, PausableOperation::Dispute, &escrow.token_address)?;
 
         if caller != escrow.depositor && caller != escrow.recipient {
             return Err(Error::UnauthorizedAccess);
@@ -672,52 +1438,33 @@ impl VaultixEscrow {
         if escrow.status == EscrowStatus::Disputed {
             return Err(Error::AlreadyInDispute);
         }
-        if escrow.status != EscrowStatus::Active && escrow.status != EscrowStatus::Created {
+        // Only funded escrows hold principal a resolution could pay out
+        if escrow.status != EscrowStatus::Active {
             return Err(Error::InvalidEscrowStatus);
         }
 
-        let mut updated_milestones = Vec::new(&env);
-        for milestone in escrow.milestones.iter() {
-            let mut m = milestone.clone();
-            if m.status == MilestoneStatus::Pending {
-                m.status = MilestoneStatus::Disputed;
-            }
-            updated_milestones.push_back(m);
-        }
-
-        escrow.milestones = updated_milestones;
-        escrow.status = EscrowStatus::Disputed;
+        // Unreleased milestones read as disputed from here on without being rewritten
+        set_status(&env, escrow_id, &mut escrow, EscrowStatus::Disputed);
         escrow.resolution = Resolution::None;
-        env.storage().persistent().set(&storage_key, &escrow);
+        save_escrow(&env, escrow_id, &escrow);
 
         // Standardized Event
-        env.events().publish(
-            (
-                Symbol::new(&env, "Vaultix"),
-                Symbol::new(&env, "DisputeRaised"),
-                escrow_id,
-            ),
-            caller,
-        );
+        publish_for(&env, escrow_id, DisputeRaised { raised_by: caller });
 
         Ok(())
     }
 
     pub fn resolve_dispute(
         env: Env,
+        caller: Address,
         escrow_id: u64,
         winner: Address,
         split_winner_amount: Option<i128>,
     ) -> Result<(), Error> {
-        let arbitrator = get_arbitrator(&env)?;
-        arbitrator.require_auth();
+        require_role(&env, Role::Arbitrator, &caller)?;
 
-        let storage_key = get_storage_key(escrow_id);
-        let mut escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&storage_key)
-            .ok_or(Error::EscrowNotFound)?;
+        let mut escrow = load_escrow(&env, escrow_id)?;
+        ensure_not_paused(&env, PausableOperation::Dispute, &escrow.token_address)?;
 
         if escrow.status != EscrowStatus::Disputed {
             return Err(Error::InvalidEscrowStatus);
@@ -754,75 +1501,39 @@ impl VaultixEscrow {
             }
         };
 
-        let token_client = token::Client::new(&env, &escrow.token_address);
+        // Disputes are settled fee-free, so the depositor's fee reserve is returned in full
+        let unused_reserve = take_fee_reserve(&mut escrow);
+        if unused_reserve > 0 {
+            pay_out(&env, &escrow, &escrow.depositor, unused_reserve)?;
+        }
 
         if amount_to_winner > 0 {
-            safe_transfer(
-                &token_client,
-                &env.current_contract_address(),
-                &winner,
-                amount_to_winner,
-            )?;
+            pay_out(&env, &escrow, &winner, amount_to_winner)?;
         }
 
         if amount_to_other > 0 {
-            safe_transfer(
-                &token_client,
-                &env.current_contract_address(),
-                &other,
-                amount_to_other,
-            )?;
+            pay_out(&env, &escrow, &other, amount_to_other)?;
         }
 
         // Update accounting and milestone statuses
-        let (amount_to_recipient, resolution) = if amount_to_winner == outstanding
-            && amount_to_other == 0
-        {
-            if winner == escrow.recipient {
-                // Full payout to recipient
-                let mut updated_milestones = Vec::new(&env);
-                for milestone in escrow.milestones.iter() {
-                    let mut m = milestone.clone();
-                    if m.status != MilestoneStatus::Released {
-                        m.status = MilestoneStatus::Released;
-                    }
-                    updated_milestones.push_back(m);
-                }
-                escrow.milestones = updated_milestones;
-                (outstanding, Resolution::Recipient)
-            } else {
-                // Full refund to depositor
-                let mut updated_milestones = Vec::new(&env);
-                for milestone in escrow.milestones.iter() {
-                    let mut m = milestone.clone();
-                    if m.status == MilestoneStatus::Pending || m.status == MilestoneStatus::Disputed
-                    {
-                        m.status = MilestoneStatus::Disputed;
-                    }
-                    updated_milestones.push_back(m);
-                }
-                escrow.milestones = updated_milestones;
-                (0i128, Resolution::Depositor)
-            }
-        } else {
-            // Split resolution
-            let mut updated_milestones = Vec::new(&env);
-            for milestone in escrow.milestones.iter() {
-                let mut m = milestone.clone();
-                if m.status != MilestoneStatus::Released {
-                    m.status = MilestoneStatus::Disputed;
+        let (amount_to_recipient, resolution) =
+            if amount_to_winner == outstanding && amount_to_other == 0 {
+                if winner == escrow.recipient {
+                    // Full payout to recipient; unreleased milestones read as released
+                    (outstanding, Resolution::Recipient)
+                } else {
+                    // Full refund to depositor; unreleased milestones read as disputed
+                    (0i128, Resolution::Depositor)
                 }
-                updated_milestones.push_back(m);
-            }
-            escrow.milestones = updated_milestones;
-
-            let recipient_amount = if winner == escrow.recipient {
-                amount_to_winner
             } else {
-                amount_to_other
+                // Split resolution; unreleased milestones read as disputed
+                let recipient_amount = if winner == escrow.recipient {
+                    amount_to_winner
+                } else {
+                    amount_to_other
+                };
+                (recipient_amount, Resolution::Split)
             };
-            (recipient_amount, Resolution::Split)
-        };
 
         escrow.total_released = escrow
             .total_released
@@ -834,173 +1545,133 @@ impl VaultixEscrow {
         }
 
         escrow.resolution = resolution;
-        escrow.status = EscrowStatus::Resolved;
-        env.storage().persistent().set(&storage_key, &escrow);
+        set_status(&env, escrow_id, &mut escrow, EscrowStatus::Resolved);
+        save_escrow(&env, escrow_id, &escrow);
 
         // Standardized Event
-        env.events().publish(
-            (
-                Symbol::new(&env, "Vaultix"),
-                Symbol::new(&env, "DisputeResolved"),
-                escrow_id,
-            ),
-            (winner, amount_to_winner, amount_to_other),
+        publish_for(
+            &env,
+            escrow_id,
+            DisputeResolved {
+                winner,
+                amount_to_winner,
+                amount_to_other,
+            },
         );
 
         Ok(())
     }
 
     pub fn cancel_escrow(env: Env, escrow_id: u64) -> Result<(), Error> {
-        let storage_key = get_storage_key(escrow_id);
-        ensure_not_paused(&env)?;
-
-        let mut escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&storage_key)
-            .ok_or(Error::EscrowNotFound)?;
+        let mut escrow = load_escrow(&env, escrow_id)?;
+        ensure_not_paused(&env, PausableOperation::Refund, &escrow.token_address)?;
         escrow.depositor.require_auth();
 
-        // Debug: emit start of cancel operation
-        env.events().publish(
-            (
-                Symbol::new(&env, "Vaultix"),
-                Symbol::new(&env, "CancelStart"),
-                escrow_id,
-            ),
-            (escrow.total_amount, escrow.total_released, escrow.status),
+        // Tracing for tests, see `events`
+        #[cfg(feature = "debug-events")]
+        publish_for(
+            &env,
+            escrow_id,
+            events::CancelStart {
+                total_amount: escrow.total_amount,
+                total_released: escrow.total_released,
+                status: escrow.status,
+            },
         );
 
-        if escrow.status != EscrowStatus::Active && escrow.status != EscrowStatus::Created {
-            return Err(Error::InvalidEscrowStatus);
-        }
-        if escrow.total_released > 0 {
-            return Err(Error::MilestoneAlreadyReleased);
-        }
+        ensure_cancellable(&escrow)?;
 
-        if escrow.status == EscrowStatus::Active {
-            let token_client = token::Client::new(&env, &escrow.token_address);
-            let refund_amount = if let Ok((treasury, _)) = Self::get_config(env.clone()) {
-                let fee_bps = resolve_fee(&env, escrow_id, &escrow.token_address)?;
-                let fee = calculate_fee(escrow.total_amount, fee_bps)?;
-                // Debug: fee resolved
-                env.events().publish(
-                    (
-                        Symbol::new(&env, "Vaultix"),
-                        Symbol::new(&env, "FeeResolved"),
-                        escrow_id,
-                    ),
-                    (fee_bps, fee),
-                );
-                // Emit debug events to help trace panics in tests
-                env.events().publish(
-                    (
-                        Symbol::new(&env, "Vaultix"),
-                        Symbol::new(&env, "FeeTransferAttempt"),
-                        escrow_id,
-                    ),
-                    (fee,),
-                );
-                if fee > 0 {
-                    safe_transfer(
-                        &token_client,
-                        &env.current_contract_address(),
-                        &treasury,
-                        fee,
-                    )?;
-                }
-                let refund = escrow
-                    .total_amount
-                    .checked_sub(fee)
-                    .ok_or(Error::InvalidMilestoneAmount)?;
-                env.events().publish(
-                    (
-                        Symbol::new(&env, "Vaultix"),
-                        Symbol::new(&env, "RefundAmountComputed"),
-                        escrow_id,
-                    ),
-                    (refund,),
-                );
-                refund
-            } else {
-                escrow.total_amount
-            };
+        let (refund, fee) = if escrow.status == EscrowStatus::Active {
+            let quote = compute_cancel(&env, &mut escrow)?;
+            #[cfg(feature = "debug-events")]
+            publish_for(
+                &env,
+                escrow_id,
+                events::FeeResolved {
+                    fee_bps: quote.fee_bps,
+                    fee: quote.fee,
+                },
+            );
+            #[cfg(feature = "debug-events")]
+            publish_for(
+                &env,
+                escrow_id,
+                events::FeeTransferAttempt { fee: quote.fee },
+            );
+            accrue_fee(&env, &escrow.token_address, quote.fee)?;
+            let refund_amount = quote.refund;
+            #[cfg(feature = "debug-events")]
+            publish_for(
+                &env,
+                escrow_id,
+                events::RefundAmountComputed {
+                    refund: refund_amount,
+                },
+            );
 
             if refund_amount > 0 {
-                safe_transfer(
-                    &token_client,
-                    &env.current_contract_address(),
-                    &escrow.depositor,
-                    refund_amount,
-                )?;
+                pay_out(&env, &escrow, &escrow.depositor, refund_amount)?;
             }
-        }
+            (refund_amount, quote.fee)
+        } else {
+            (0, 0)
+        };
 
-        escrow.status = EscrowStatus::Cancelled;
-        env.storage().persistent().set(&storage_key, &escrow);
-        env.storage()
-            .persistent()
-            .extend_ttl(&storage_key, 100, 2_000_000);
+        set_status(&env, escrow_id, &mut escrow, EscrowStatus::Cancelled);
+        save_escrow(&env, escrow_id, &escrow);
 
         // Standardized Event
-        env.events().publish(
-            (
-                Symbol::new(&env, "Vaultix"),
-                Symbol::new(&env, "EscrowCancelled"),
-                escrow_id,
-            ),
-            escrow.depositor.clone(),
+        publish_for(
+            &env,
+            escrow_id,
+            EscrowCancelled {
+                depositor: escrow.depositor.clone(),
+                refund,
+                fee,
+            },
         );
 
         Ok(())
     }
 
     pub fn complete_escrow(env: Env, escrow_id: u64) -> Result<(), Error> {
-        let storage_key = get_storage_key(escrow_id);
-        ensure_not_paused(&env)?;
-
-        let mut escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&storage_key)
-            .ok_or(Error::EscrowNotFound)?;
+        let mut escrow = load_escrow(&env, escrow_id)?;
+        ensure_not_paused(&env, PausableOperation::Release, &escrow.token_address)?;
         escrow.depositor.require_auth();
 
         if escrow.status != EscrowStatus::Active {
             return Err(Error::InvalidEscrowStatus);
         }
-        if !verify_all_released(&escrow.milestones) {
+        if escrow.released_count < escrow.milestone_count {
             return Err(Error::EscrowNotActive);
         }
 
-        escrow.status = EscrowStatus::Completed;
-        env.storage().persistent().set(&storage_key, &escrow);
-        env.storage()
-            .persistent()
-            .extend_ttl(&storage_key, 100, 2_000_000);
+        // Return any fee reserve left over from rounding or fee-free releases
+        let unused_reserve = take_fee_reserve(&mut escrow);
+        if unused_reserve > 0 {
+            pay_out(&env, &escrow, &escrow.depositor, unused_reserve)?;
+        }
+
+        set_status(&env, escrow_id, &mut escrow, EscrowStatus::Completed);
+        save_escrow(&env, escrow_id, &escrow);
 
         // Standardized Event
-        env.events().publish(
-            (
-                Symbol::new(&env, "Vaultix"),
-                Symbol::new(&env, "EscrowCompleted"),
-                escrow_id,
-            ),
-            (),
+        publish_for(
+            &env,
+            escrow_id,
+            EscrowCompleted {
+                total_released: escrow.total_released,
+                reserve_refunded: unused_reserve,
+            },
         );
 
         Ok(())
     }
 
     pub fn refund_expired(env: Env, escrow_id: u64, caller: Address) -> Result<(), Error> {
-        let storage_key = get_storage_key(escrow_id);
-
         // Load escrow from storage
-        let mut escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&storage_key)
-            .ok_or(Error::EscrowNotFound)?;
+        let mut escrow = load_escrow(&env, escrow_id)?;
+        ensure_not_paused(&env, PausableOperation::Refund, &escrow.token_address)?;
 
         // Validate deadline has passed
         let current_time = env.ledger().timestamp();
@@ -1019,88 +1690,431 @@ impl VaultixEscrow {
             return Err(Error::Unauthorized);
         }
 
-        // Calculate remaining balance
-        let remaining_balance = escrow
-            .total_amount
-            .checked_sub(escrow.total_released)
-            .ok_or(Error::InvalidMilestoneAmount)?;
+        // Calculate remaining balance, failing if there are no funds to refund
+        let remaining_balance = get_refundable_balance(&escrow)?;
 
-        // Check if there are funds to refund
-        if remaining_balance <= 0 {
-            return Err(Error::NoFundsToRefund);
-        }
+        // Fees accrue on behalf of the treasury, so it must be configured
+        Self::get_config(env.clone())?;
 
-        // Retrieve platform fee BPS from contract configuration
-        let (treasury, _) = Self::get_config(env.clone())?;
-
-        // Resolve fee with precedence: escrow > token > global
-        let fee_bps = resolve_fee(&env, escrow_id, &escrow.token_address)?;
-
-        // Calculate platform fee using checked arithmetic
-        let platform_fee = calculate_fee(remaining_balance, fee_bps)?;
-
-        // Calculate refund amount
-        let refund_amount = remaining_balance
-            .checked_sub(platform_fee)
-            .ok_or(Error::InvalidMilestoneAmount)?;
+        // Calculate platform fee from the escrow's fee snapshot and the refund amount,
+        // returning any unused fee reserve to the buyer
+        let SettlementQuote {
+            fee: platform_fee,
+            refund: refund_amount,
+            ..
+        } = compute_refund(&mut escrow, remaining_balance, true)?;
 
         // Get token client for escrow's token address
-        let token_client = token::Client::new(&env, &escrow.token_address);
 
         // Transfer refund amount to buyer
-        safe_transfer(
-            &token_client,
-            &env.current_contract_address(),
-            &escrow.depositor,
-            refund_amount,
-        )?;
+        pay_out(&env, &escrow, &escrow.depositor, refund_amount)?;
 
-        // If platform fee > 0, transfer fee to fee recipient
-        if platform_fee > 0 {
-            safe_transfer(
-                &token_client,
-                &env.current_contract_address(),
-                &treasury,
-                platform_fee,
-            )?;
-        }
+        // Accrue platform fee for later withdrawal by the treasury
+        accrue_fee(&env, &escrow.token_address, platform_fee)?;
 
         // Update escrow state
-        escrow.status = EscrowStatus::Expired;
+        set_status(&env, escrow_id, &mut escrow, EscrowStatus::Expired);
         escrow.total_released = escrow.total_amount;
-        env.storage().persistent().set(&storage_key, &escrow);
-        env.storage()
-            .persistent()
-            .extend_ttl(&storage_key, 100, 2_000_000);
+        save_escrow(&env, escrow_id, &escrow);
 
         // Emit RefundEvent
-        env.events().publish(
-            (
-                Symbol::new(&env, "Vaultix"),
-                Symbol::new(&env, "RefundExpired"),
-                escrow_id,
-            ),
-            (escrow.depositor.clone(), refund_amount, current_time),
+        publish_for(
+            &env,
+            escrow_id,
+            RefundExpired {
+                depositor: escrow.depositor.clone(),
+                refund: refund_amount,
+                timestamp: current_time,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Withdraw all unreleased funds of an active escrow once pauses have blocked
+    /// every way out of it for longer than the emergency exit delay.
+    /// Only the depositor can call. No fee is charged, and the escrow is cancelled.
+    /// Disputed escrows are left to the arbitrator to resolve.
+    pub fn emergency_withdraw(env: Env, escrow_id: u64) -> Result<(), Error> {
+        let mut escrow = load_escrow(&env, escrow_id)?;
+        escrow.depositor.require_auth();
+        ensure_migrated(&env)?;
+
+        if escrow.status != EscrowStatus::Active {
+            return Err(Error::InvalidEscrowStatus);
+        }
+
+        let blocked_since = get_exit_blocked_since(&env, &escrow.token_address)
+            .ok_or(Error::EmergencyExitUnavailable)?;
+        let opens_at = blocked_since.saturating_add(get_emergency_exit_delay(&env));
+        if env.ledger().timestamp() < opens_at {
+            return Err(Error::EmergencyExitUnavailable);
+        }
+
+        let remaining_balance = get_refundable_balance(&escrow)?;
+        let refund_amount = remaining_balance
+            .checked_add(take_fee_reserve(&mut escrow))
+            .ok_or(Error::InvalidMilestoneAmount)?;
+
+        pay_out(&env, &escrow, &escrow.depositor, refund_amount)?;
+
+        set_status(&env, escrow_id, &mut escrow, EscrowStatus::Cancelled);
+        save_escrow(&env, escrow_id, &escrow);
+
+        publish_for(
+            &env,
+            escrow_id,
+            EmergencyWithdrawal {
+                depositor: escrow.depositor.clone(),
+                refund: refund_amount,
+            },
         );
 
         Ok(())
     }
 }
 
-fn get_storage_key(escrow_id: u64) -> (Symbol, u64) {
-    (symbol_short!("escrow"), escrow_id)
+fn extend_instance_ttl(env: &Env) {
+    env.storage()
+        .instance()
+        .extend_ttl(INSTANCE_TTL_THRESHOLD, INSTANCE_TTL_EXTEND_TO);
 }
 
-/// Generates storage key for token-specific fee override
-/// Returns a tuple of (Symbol, Address) for scoped storage access
-fn get_token_fee_key(token_address: &Address) -> (Symbol, Address) {
-    (symbol_short!("tokfee"), token_address.clone())
+fn extend_persistent_ttl<K>(env: &Env, key: &K)
+where
+    K: IntoVal<Env, Val>,
+{
+    env.storage()
+        .persistent()
+        .extend_ttl(key, PERSISTENT_TTL_THRESHOLD, PERSISTENT_TTL_EXTEND_TO);
+}
+
+/// Reads a persistent entry, extending its TTL when present.
+fn get_persistent<K, V>(env: &Env, key: &K) -> Option<V>
+where
+    K: IntoVal<Env, Val>,
+    V: TryFromVal<Env, Val>,
+{
+    let value = env.storage().persistent().get(key);
+    if value.is_some() {
+        extend_persistent_ttl(env, key);
+    }
+    value
+}
+
+/// Writes a persistent entry and extends its TTL.
+fn set_persistent<K, V>(env: &Env, key: &K, value: &V)
+where
+    K: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val>,
+{
+    env.storage().persistent().set(key, value);
+    extend_persistent_ttl(env, key);
 }
 
-/// Generates storage key for escrow-specific fee override
-/// Returns a tuple of (Symbol, u64) for scoped storage access
-fn get_escrow_fee_key(escrow_id: u64) -> (Symbol, u64) {
-    (symbol_short!("escfee"), escrow_id)
+/// Loads an escrow, keeping both it and the contract instance alive.
+fn load_escrow(env: &Env, escrow_id: u64) -> Result<Escrow, Error> {
+    extend_instance_ttl(env);
+    if let Some(escrow) = get_persistent(env, &DataKey::Escrow(escrow_id)) {
+        return Ok(escrow);
+    }
+    // Version 1 escrows left out of the migration move over on first use
+    if get_contract_version(env) >= CONTRACT_VERSION && migrate_escrow_v1(env, escrow_id)? {
+        return get_persistent(env, &DataKey::Escrow(escrow_id)).ok_or(Error::EscrowNotFound);
+    }
+    Err(Error::EscrowNotFound)
+}
+
+fn save_escrow(env: &Env, escrow_id: u64, escrow: &Escrow) {
+    extend_instance_ttl(env);
+    set_persistent(env, &DataKey::Escrow(escrow_id), escrow);
+}
+
+/// Loads a milestone as stored, failing if `milestone_index` is out of range.
+fn load_milestone(
+    env: &Env,
+    escrow: &Escrow,
+    escrow_id: u64,
+    milestone_index: u32,
+) -> Result<Milestone, Error> {
+    if milestone_index >= escrow.milestone_count {
+        return Err(Error::MilestoneNotFound);
+    }
+    get_persistent(env, &DataKey::Milestone(escrow_id, milestone_index))
+        .ok_or(Error::MilestoneNotFound)
+}
+
+/// Loads a milestone with the status implied by the escrow's dispute state.
+/// Only releases are written to milestone entries; disputes and their
+/// resolutions apply to every unreleased milestone through the escrow header.
+fn load_milestone_view(
+    env: &Env,
+    escrow: &Escrow,
+    escrow_id: u64,
+    milestone_index: u32,
+) -> Result<Milestone, Error> {
+    let mut milestone = load_milestone(env, escrow, escrow_id, milestone_index)?;
+    if milestone.status != MilestoneStatus::Released {
+        milestone.status = match (escrow.status, escrow.resolution) {
+            (EscrowStatus::Resolved, Resolution::Recipient) => MilestoneStatus::Released,
+            (EscrowStatus::Disputed, _) | (EscrowStatus::Resolved, _) => MilestoneStatus::Disputed,
+            _ => milestone.status,
+        };
+    }
+    Ok(milestone)
+}
+
+fn save_milestone(env: &Env, escrow_id: u64, milestone_index: u32, milestone: &Milestone) {
+    set_persistent(
+        env,
+        &DataKey::Milestone(escrow_id, milestone_index),
+        milestone,
+    );
+}
+
+/// Stores `milestones` as pending from `first_index` on.
+fn save_new_milestones(env: &Env, escrow_id: u64, first_index: u32, milestones: &Vec<Milestone>) {
+    for (offset, milestone) in milestones.iter().enumerate() {
+        let mut m = milestone.clone();
+        m.status = MilestoneStatus::Pending;
+        save_milestone(env, escrow_id, first_index + offset as u32, &m);
+    }
+}
+
+/// Validates and stores a new escrow in the `Created` state. Callers check auth.
+fn open_escrow(
+    env: &Env,
+    escrow_id: u64,
+    depositor: &Address,
+    recipient: &Address,
+    token_address: &Address,
+    milestones: &Vec<Milestone>,
+    deadline: u64,
+) -> Result<Escrow, Error> {
+    ensure_not_paused(env, PausableOperation::Create, token_address)?;
+
+    if depositor == recipient {
+        return Err(Error::SelfDealing);
+    }
+    ensure_screened(env, depositor)?;
+    ensure_screened(env, recipient)?;
+
+    // IDs of archived escrows stay taken so their receipts and index entries hold
+    if escrow_exists(env, escrow_id) {
+        return Err(Error::EscrowAlreadyExists);
+    }
+
+    let total_amount = validate_milestones(milestones)?;
+    ensure_token_allowed(env, token_address, total_amount)?;
+    let fee_bps = resolve_fee(env, escrow_id, token_address, depositor, recipient)?;
+
+    save_new_milestones(env, escrow_id, 0, milestones);
+
+    let escrow = Escrow {
+        depositor: depositor.clone(),
+        recipient: recipient.clone(),
+        token_address: token_address.clone(),
+        total_amount,
+        total_released: 0,
+        milestone_count: milestones.len(),
+        released_count: 0,
+        status: EscrowStatus::Created,
+        deadline,
+        resolution: Resolution::None,
+        fee_payer: FeePayer::Recipient,
+        fee_reserve: 0,
+        fee_bps,
+    };
+
+    save_escrow(env, escrow_id, &escrow);
+    index_escrow(env, escrow_id, depositor, recipient);
+    record_status_change(env, None, EscrowStatus::Created);
+
+    // Standardized Event
+    publish_for(
+        env,
+        escrow_id,
+        EscrowCreated {
+            depositor: depositor.clone(),
+            recipient: recipient.clone(),
+            token_address: token_address.clone(),
+            total_amount,
+            deadline,
+        },
+    );
+
+    Ok(escrow)
+}
+
+fn apply_fee_change_delay(env: &Env, delay: u64) -> Result<(), Error> {
+    let mut config = get_stored_config(env).ok_or(Error::AdminNotInitialized)?;
+    let old_delay = config.fee_change_delay;
+    config.fee_change_delay = delay;
+    set_stored_config(env, &config);
+
+    publish(
+        env,
+        FeeChangeDelayUpdated {
+            old_delay,
+            new_delay: delay,
+        },
+    );
+
+    Ok(())
+}
+
+fn get_fee_change_delay(env: &Env) -> u64 {
+    get_stored_config(env)
+        .map(|config| config.fee_change_delay)
+        .unwrap_or(DEFAULT_FEE_CHANGE_DELAY)
+}
+
+/// Records a fee change to take effect after the configured delay.
+/// A zero delay applies the change immediately.
+fn schedule_fee_change(
+    env: &Env,
+    token_address: Option<Address>,
+    fee_bps: i128,
+) -> Result<(), Error> {
+    if !(0..=BPS_DENOMINATOR).contains(&fee_bps) {
+        return Err(Error::InvalidFeeConfiguration);
+    }
+
+    let eta = env
+        .ledger()
+        .timestamp()
+        .checked_add(get_fee_change_delay(env))
+        .ok_or(Error::InvalidFeeConfiguration)?;
+
+    publish_for(
+        env,
+        token_address.clone(),
+        FeeChangeScheduled { fee_bps, eta },
+    );
+
+    let pending_key = pending_fee_key(&token_address);
+    if eta <= env.ledger().timestamp() {
+        env.storage().persistent().remove(&pending_key);
+        apply_fee_value(env, &token_address, fee_bps);
+    } else {
+        set_persistent(env, &pending_key, &PendingFeeChange { fee_bps, eta });
+    }
+
+    Ok(())
+}
+
+/// Writes a global (`None`) or token-level fee and emits `FeeChangeApplied`.
+fn apply_fee_value(env: &Env, token_address: &Option<Address>, fee_bps: i128) {
+    let old_fee = match token_address {
+        None => match get_stored_config(env) {
+            Some(mut config) => {
+                let old_fee = config.fee_bps;
+                config.fee_bps = fee_bps;
+                set_stored_config(env, &config);
+                old_fee
+            }
+            None => DEFAULT_FEE_BPS,
+        },
+        Some(token_address) => {
+            let token_fee_key = DataKey::TokenFee(token_address.clone());
+            let old_fee: Option<i128> = get_persistent(env, &token_fee_key);
+            set_persistent(env, &token_fee_key, &fee_bps);
+            old_fee.unwrap_or(DEFAULT_FEE_BPS)
+        }
+    };
+
+    publish_for(
+        env,
+        token_address.clone(),
+        FeeChangeApplied {
+            old_fee_bps: old_fee,
+            new_fee_bps: fee_bps,
+        },
+    );
+}
+
+/// Fails unless `fee_bps` is a valid fee that does not raise the snapshot of a
+/// funded escrow.
+fn validate_escrow_fee(env: &Env, escrow_id: u64, fee_bps: i128) -> Result<(), Error> {
+    if !(0..=BPS_DENOMINATOR).contains(&fee_bps) {
+        return Err(Error::InvalidFeeConfiguration);
+    }
+    if let Ok(escrow) = load_escrow(env, escrow_id) {
+        if escrow.status != EscrowStatus::Created && fee_bps > escrow.fee_bps {
+            return Err(Error::InvalidFeeConfiguration);
+        }
+    }
+    Ok(())
+}
+
+/// Sets a per-escrow fee override and updates the escrow's fee snapshot.
+/// Once an escrow is funded its snapshot can only be lowered, never raised.
+fn apply_escrow_fee(env: &Env, escrow_id: u64, fee_bps: i128) -> Result<(), Error> {
+    validate_escrow_fee(env, escrow_id, fee_bps)?;
+
+    if let Ok(mut escrow) = load_escrow(env, escrow_id) {
+        escrow.fee_bps = fee_bps;
+        save_escrow(env, escrow_id, &escrow);
+    }
+
+    let escrow_fee_key = DataKey::EscrowFee(escrow_id);
+    let old_fee: Option<i128> = get_persistent(env, &escrow_fee_key);
+
+    set_persistent(env, &escrow_fee_key, &fee_bps);
+
+    // Emit FeeUpdated event for escrow-level override
+    publish_for(
+        env,
+        escrow_id,
+        EscrowFeeUpdated {
+            old_fee_bps: old_fee.unwrap_or(DEFAULT_FEE_BPS),
+            new_fee_bps: fee_bps,
+        },
+    );
+
+    Ok(())
+}
+
+/// Fails unless `discount` takes between nothing and the whole fee off and has
+/// not already lapsed.
+fn validate_fee_discount(env: &Env, discount: &FeeDiscount) -> Result<(), Error> {
+    if discount.discount_bps <= 0 || discount.discount_bps > BPS_DENOMINATOR {
+        return Err(Error::InvalidFeeConfiguration);
+    }
+    if let Some(until) = discount.valid_until {
+        if until <= env.ledger().timestamp() {
+            return Err(Error::InvalidDeadline);
+        }
+    }
+    Ok(())
+}
+
+fn apply_fee_discount(env: &Env, addr: Address, discount: FeeDiscount) -> Result<(), Error> {
+    validate_fee_discount(env, &discount)?;
+    set_persistent(env, &DataKey::FeeDiscount(addr.clone()), &discount);
+
+    publish_for(
+        env,
+        addr,
+        FeeDiscountAdded {
+            discount_bps: discount.discount_bps,
+            valid_until: discount.valid_until,
+        },
+    );
+
+    Ok(())
+}
+
+fn apply_fee_discount_removal(env: &Env, addr: Address, actor: Address) -> Result<(), Error> {
+    let discount_key = DataKey::FeeDiscount(addr.clone());
+    if !env.storage().persistent().has(&discount_key) {
+        return Err(Error::FeeDiscountNotFound);
+    }
+    env.storage().persistent().remove(&discount_key);
+
+    publish_for(env, addr, FeeDiscountRemoved { removed_by: actor });
+
+    Ok(())
 }
 
 /// Resolves the applicable fee for a transaction using the following precedence:
@@ -1108,42 +2122,265 @@ fn get_escrow_fee_key(escrow_id: u64) -> (Symbol, u64) {
 /// 2. Per-token fee override
 /// 3. Global default fee (fallback)
 ///
+/// The largest active address discount held by either party is then applied on top.
+///
+/// Called when an escrow is created; the result is snapshotted into `Escrow::fee_bps`
+/// so later configuration changes do not alter what existing escrows pay.
+///
 /// # Arguments
 /// * `env` - Soroban environment reference
 /// * `escrow_id` - ID of the escrow transaction
 /// * `token_address` - Token being transferred
+/// * `depositor` - Depositor of the escrow
+/// * `recipient` - Recipient of the escrow
 ///
 /// # Returns
 /// The fee in basis points to apply for the transaction
-fn resolve_fee(env: &Env, escrow_id: u64, token_address: &Address) -> Result<i128, Error> {
-    // Check escrow-specific override first (highest priority)
-    let escrow_fee_key = get_escrow_fee_key(escrow_id);
-    if let Some(escrow_fee) = env
-        .storage()
-        .persistent()
-        .get::<(Symbol, u64), i128>(&escrow_fee_key)
-    {
-        return Ok(escrow_fee);
+fn resolve_fee(
+    env: &Env,
+    escrow_id: u64,
+    token_address: &Address,
+    depositor: &Address,
+    recipient: &Address,
+) -> Result<i128, Error> {
+    let escrow_fee_key = DataKey::EscrowFee(escrow_id);
+    let token_fee_key = DataKey::TokenFee(token_address.clone());
+
+    // Check escrow-specific override first (highest priority), then token-specific
+    // override, falling back to the global default fee
+    let base_fee = if let Some(escrow_fee) = get_persistent::<_, i128>(env, &escrow_fee_key) {
+        escrow_fee
+    } else if let Some(token_fee) = get_persistent::<_, i128>(env, &token_fee_key) {
+        token_fee
+    } else {
+        get_stored_config(env)
+            .map(|config| config.fee_bps)
+            .unwrap_or(DEFAULT_FEE_BPS)
+    };
+
+    let discount_bps = active_discount_bps(env, depositor).max(active_discount_bps(env, recipient));
+    if discount_bps == 0 {
+        return Ok(base_fee);
     }
 
-    // Check token-specific override second
-    let token_fee_key = get_token_fee_key(token_address);
-    if let Some(token_fee) = env
-        .storage()
-        .persistent()
-        .get::<(Symbol, Address), i128>(&token_fee_key)
-    {
-        return Ok(token_fee);
+    let remaining_bps = BPS_DENOMINATOR
+        .checked_sub(discount_bps)
+        .ok_or(Error::InvalidFeeConfiguration)?;
+    calculate_fee(base_fee, remaining_bps)
+}
+
+/// Returns the discount (in basis points off the fee) currently granted to `addr`,
+/// or 0 if it has none or its discount has expired.
+fn active_discount_bps(env: &Env, addr: &Address) -> i128 {
+    let discount: Option<FeeDiscount> = get_persistent(env, &DataKey::FeeDiscount(addr.clone()));
+    match discount {
+        Some(discount) => match discount.valid_until {
+            Some(until) if env.ledger().timestamp() > until => 0,
+            _ => discount.discount_bps,
+        },
+        None => 0,
     }
+}
 
-    // Fall back to global default fee
-    let global_fee: i128 = env
-        .storage()
-        .instance()
-        .get(&symbol_short!("fee_bps"))
-        .unwrap_or(DEFAULT_FEE_BPS);
+/// Records a platform fee retained by the contract for later treasury withdrawal.
+/// Fees stay in the contract balance until `withdraw_fees` is called.
+fn accrue_fee(env: &Env, token_address: &Address, fee: i128) -> Result<(), Error> {
+    if fee <= 0 {
+        return Ok(());
+    }
+    // The fee stops being owed to the escrow and is owed to the treasury instead
+    release_liabilities(env, token_address, fee);
+    record_fee_collected(env, token_address, fee)?;
+    let accrued_key = DataKey::AccruedFees(token_address.clone());
+    let accrued: i128 = get_persistent(env, &accrued_key).unwrap_or(0);
+    let updated = accrued
+        .checked_add(fee)
+        .ok_or(Error::InvalidMilestoneAmount)?;
+    set_persistent(env, &accrued_key, &updated);
+    Ok(())
+}
+
+/// Applies a computed platform fee to a settlement of `amount` according to the
+/// escrow's fee payer.
+///
+/// Returns `(payout, fee_charged)`. In recipient-pays mode the fee is deducted from
+/// the payout; in depositor-pays mode the payout is the full amount and the fee is
+/// drawn from the escrow's fee reserve, never exceeding what was reserved.
+fn charge_fee(escrow: &mut Escrow, amount: i128, fee: i128) -> Result<(i128, i128), Error> {
+    match escrow.fee_payer {
+        FeePayer::Recipient => {
+            let payout = amount
+                .checked_sub(fee)
+                .ok_or(Error::InvalidMilestoneAmount)?;
+            Ok((payout, fee))
+        }
+        FeePayer::Depositor => {
+            let charged = fee.min(escrow.fee_reserve);
+            escrow.fee_reserve = escrow
+                .fee_reserve
+                .checked_sub(charged)
+                .ok_or(Error::InvalidMilestoneAmount)?;
+            Ok((amount, charged))
+        }
+    }
+}
+
+/// Computes the fee and recipient payout for releasing `amount`.
+/// Shared by `release_milestone` and `quote_release`.
+fn compute_release(escrow: &mut Escrow, amount: i128) -> Result<SettlementQuote, Error> {
+    let fee = calculate_fee(amount, escrow.fee_bps)?;
+    let (payout, fee) = charge_fee(escrow, amount, fee)?;
+    Ok(SettlementQuote {
+        fee_bps: escrow.fee_bps,
+        fee,
+        payout,
+        refund: 0,
+    })
+}
+
+/// Computes the fee and depositor refund for returning `remaining` to the depositor,
+/// including any unused fee reserve. No fee is charged when `charge_fee_bps` is false.
+/// Shared by cancellation, expiry refunds and their quotes.
+fn compute_refund(
+    escrow: &mut Escrow,
+    remaining: i128,
+    charge_fee_bps: bool,
+) -> Result<SettlementQuote, Error> {
+    let fee_bps = if charge_fee_bps { escrow.fee_bps } else { 0 };
+    let fee = calculate_fee(remaining, fee_bps)?;
+    let (refund, fee) = charge_fee(escrow, remaining, fee)?;
+    let refund = refund
+        .checked_add(take_fee_reserve(escrow))
+        .ok_or(Error::InvalidMilestoneAmount)?;
+    Ok(SettlementQuote {
+        fee_bps,
+        fee,
+        payout: 0,
+        refund,
+    })
+}
+
+/// Computes the cancellation refund of a funded escrow. A fee is only retained
+/// when the treasury is configured.
+fn compute_cancel(env: &Env, escrow: &mut Escrow) -> Result<SettlementQuote, Error> {
+    let treasury_configured = get_stored_config(env).is_some();
+    let total_amount = escrow.total_amount;
+    compute_refund(escrow, total_amount, treasury_configured)
+}
+
+/// Clears the escrow's remaining fee reserve and returns it for refunding to the depositor.
+fn take_fee_reserve(escrow: &mut Escrow) -> i128 {
+    let reserve = escrow.fee_reserve;
+    escrow.fee_reserve = 0;
+    reserve
+}
+
+fn get_liabilities(env: &Env, token_address: &Address) -> i128 {
+    get_persistent(env, &DataKey::Liabilities(token_address.clone())).unwrap_or(0)
+}
+
+fn add_liabilities(env: &Env, token_address: &Address, amount: i128) -> Result<(), Error> {
+    let updated = get_liabilities(env, token_address)
+        .checked_add(amount)
+        .ok_or(Error::InvalidMilestoneAmount)?;
+    set_persistent(env, &DataKey::Liabilities(token_address.clone()), &updated);
+    Ok(())
+}
+
+/// Reduces liabilities, saturating at zero.
+fn release_liabilities(env: &Env, token_address: &Address, amount: i128) {
+    let updated = get_liabilities(env, token_address)
+        .saturating_sub(amount)
+        .max(0);
+    set_persistent(env, &DataKey::Liabilities(token_address.clone()), &updated);
+}
+
+/// Pulls `amount` from `depositor` into the contract and records it as liabilities.
+fn pull_funds(
+    env: &Env,
+    token_address: &Address,
+    depositor: &Address,
+    amount: i128,
+) -> Result<(), Error> {
+    let token_client = token::Client::new(env, token_address);
+    // Defensive checks to avoid host traps when the token contract would trap
+    // on transfer_from due to missing allowance or insufficient balance.
+    // Check depositor balance first.
+    let depositor_balance = token_client.balance(depositor);
+    if depositor_balance < amount {
+        return Err(Error::InsufficientBalance);
+    }
+
+    // Check allowance granted to this contract (spender) by the depositor.
+    // If allowance is insufficient, return a TokenTransferFailed error instead
+    // of invoking transfer_from which would trap the host.
+    let spender = env.current_contract_address();
+    let allowance = token_client.allowance(depositor, &spender);
+    if allowance < amount {
+        return Err(Error::TokenTransferFailed);
+    }
+
+    // Safe to call transfer_from now that basic preconditions hold.
+    token_client.transfer_from(&spender, depositor, &spender, &amount);
+    add_liabilities(env, token_address, amount)
+}
 
-    Ok(global_fee)
+/// Activates an escrow whose funds have been pulled in.
+fn mark_funded(env: &Env, escrow_id: u64, escrow: &mut Escrow, fee_reserve: i128) {
+    escrow.fee_reserve = fee_reserve;
+    set_status(env, escrow_id, escrow, EscrowStatus::Active);
+    save_escrow(env, escrow_id, escrow);
+
+    // Standardized Event
+    publish_for(
+        env,
+        escrow_id,
+        EscrowFunded {
+            amount: escrow.total_amount,
+            fee_reserve: escrow.fee_reserve,
+        },
+    );
+}
+
+/// Pays funds held for `escrow` out of the contract, releasing them from
+/// liabilities and recording them in the token's statistics.
+/// In credit mode the payee is credited instead and liabilities are released
+/// on withdrawal. Payouts to the recipient are screened; refunds to the
+/// depositor are not, so funds never get stuck behind a later block.
+fn pay_out(env: &Env, escrow: &Escrow, to: &Address, amount: i128) -> Result<(), Error> {
+    if amount <= 0 {
+        return Ok(());
+    }
+    if *to == escrow.recipient {
+        ensure_screened(env, to)?;
+    }
+    if is_credit_mode(env) {
+        add_credit(env, to, &escrow.token_address, amount)?;
+    } else {
+        let token_client = token::Client::new(env, &escrow.token_address);
+        safe_transfer(&token_client, &env.current_contract_address(), to, amount)?;
+        release_liabilities(env, &escrow.token_address, amount);
+    }
+    record_payout(env, escrow, to, amount)
+}
+
+/// Moves the token balance not owed to escrows or the treasury to `to`.
+fn apply_sweep(env: &Env, token_address: Address, to: Address) -> Result<i128, Error> {
+    // Escrows not yet migrated hold funds missing from the liabilities
+    ensure_migrated(env)?;
+    let token_client = token::Client::new(env, &token_address);
+    let owed = get_liabilities(env, &token_address)
+        .checked_add(get_persistent(env, &DataKey::AccruedFees(token_address.clone())).unwrap_or(0))
+        .ok_or(Error::InvalidMilestoneAmount)?;
+    let excess = token_client
+        .balance(&env.current_contract_address())
+        .saturating_sub(owed)
+        .max(0);
+    safe_transfer(&token_client, &env.current_contract_address(), &to, excess)?;
+
+    publish_for(env, token_address, ExcessSwept { to, amount: excess });
+
+    Ok(excess)
 }
 
 /// Safely transfer tokens from `from` to `to`, returning an error if balance is insufficient.
@@ -1164,31 +2401,343 @@ fn safe_transfer(
     Ok(())
 }
 
-fn ensure_not_paused(env: &Env) -> Result<(), Error> {
-    let state: ContractState = env
-        .storage()
-        .instance()
-        .get(&symbol_short!("state"))
+/// Fails when `operation` is paused globally, for the operation, or for `token_address`.
+fn ensure_not_paused(
+    env: &Env,
+    operation: PausableOperation,
+    token_address: &Address,
+) -> Result<(), Error> {
+    ensure_migrated(env)?;
+    let instance = env.storage().instance();
+    let state: ContractState = instance
+        .get(&DataKey::PauseState)
+        .unwrap_or(ContractState::Active);
+    if state == ContractState::Paused
+        || instance.has(&DataKey::OperationPaused(operation))
+        || instance.has(&DataKey::TokenPaused(token_address.clone()))
+    {
+        return Err(Error::ContractPaused);
+    }
+    Ok(())
+}
+
+/// Returns when the earliest active pause covering `operation` began, if any.
+/// A global pause without a recorded start counts from now.
+fn get_paused_since(
+    env: &Env,
+    operation: PausableOperation,
+    token_address: Option<&Address>,
+) -> Option<u64> {
+    let instance = env.storage().instance();
+    let state: ContractState = instance
+        .get(&DataKey::PauseState)
         .unwrap_or(ContractState::Active);
+
+    let mut since: Option<u64> = None;
     if state == ContractState::Paused {
+        since = Some(
+            instance
+                .get(&DataKey::PausedAt)
+                .unwrap_or(env.ledger().timestamp()),
+        );
+    }
+    let operation_since: Option<u64> = instance.get(&DataKey::OperationPaused(operation));
+    let token_since: Option<u64> =
+        token_address.and_then(|t| instance.get(&DataKey::TokenPaused(t.clone())));
+    for candidate in [operation_since, token_since].into_iter().flatten() {
+        since = Some(since.map_or(candidate, |s| s.min(candidate)));
+    }
+    since
+}
+
+/// Returns since when pauses have blocked every way out of an active escrow in
+/// `token_address`, if they do: a release, a refund and a dispute.
+fn get_exit_blocked_since(env: &Env, token_address: &Address) -> Option<u64> {
+    let exits = [
+        PausableOperation::Release,
+        PausableOperation::Refund,
+        PausableOperation::Dispute,
+    ];
+
+    let mut blocked_since = 0;
+    for operation in exits {
+        let since = get_paused_since(env, operation, Some(token_address))?;
+        blocked_since = blocked_since.max(since);
+    }
+    Some(blocked_since)
+}
+
+fn get_emergency_exit_delay(env: &Env) -> u64 {
+    get_stored_config(env)
+        .map(|config| config.emergency_exit_delay)
+        .unwrap_or(DEFAULT_EMERGENCY_EXIT_DELAY)
+}
+
+fn apply_emergency_exit_delay(env: &Env, delay: u64) -> Result<(), Error> {
+    let mut config = get_stored_config(env).ok_or(Error::AdminNotInitialized)?;
+    let delay = delay.min(MAX_EMERGENCY_EXIT_DELAY);
+    config.emergency_exit_delay = delay;
+    set_stored_config(env, &config);
+
+    publish(env, EmergencyExitDelayUpdated { delay });
+
+    Ok(())
+}
+
+fn apply_paused(env: &Env, paused: bool, actor: Address) {
+    let state = if paused {
+        ContractState::Paused
+    } else {
+        ContractState::Active
+    };
+    env.storage().instance().set(&DataKey::PauseState, &state);
+    set_pause_marker(env, &DataKey::PausedAt, paused);
+
+    publish(env, PausedStateChanged { paused, actor });
+}
+
+fn apply_operation_paused(env: &Env, operation: PausableOperation, paused: bool, actor: Address) {
+    set_pause_marker(env, &DataKey::OperationPaused(operation), paused);
+
+    publish(
+        env,
+        OperationPaused {
+            operation,
+            paused,
+            actor,
+        },
+    );
+}
+
+fn apply_token_paused(env: &Env, token_address: Address, paused: bool, actor: Address) {
+    set_pause_marker(env, &DataKey::TokenPaused(token_address.clone()), paused);
+
+    publish(
+        env,
+        TokenPaused {
+            token_address,
+            paused,
+            actor,
+        },
+    );
+}
+
+/// Stores the time a pause began under `key`, keeping the original time when
+/// pausing again, or clears it when unpausing.
+fn set_pause_marker<K>(env: &Env, key: &K, paused: bool)
+where
+    K: IntoVal<Env, Val>,
+{
+    let instance = env.storage().instance();
+    if !paused {
+        instance.remove(key);
+    } else if !instance.has(key) {
+        instance.set(key, &env.ledger().timestamp());
+    }
+}
+
+/// Records an upgrade to `new_wasm_hash` executable after `UPGRADE_DELAY` seconds.
+fn schedule_upgrade(env: &Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+    let eta = env
+        .ledger()
+        .timestamp()
+        .checked_add(UPGRADE_DELAY)
+        .ok_or(Error::TimelockNotReady)?;
+    let pending = PendingUpgrade {
+        wasm_hash: new_wasm_hash.clone(),
+        eta,
+    };
+    env.storage()
+        .instance()
+        .set(&DataKey::PendingUpgrade, &pending);
+
+    publish(
+        env,
+        UpgradeProposed {
+            wasm_hash: new_wasm_hash,
+            eta,
+        },
+    );
+
+    Ok(())
+}
+
+fn unschedule_upgrade(env: &Env) -> Result<(), Error> {
+    let pending = get_pending_upgrade(env)?;
+    env.storage().instance().remove(&DataKey::PendingUpgrade);
+
+    publish(
+        env,
+        UpgradeCancelled {
+            wasm_hash: pending.wasm_hash,
+        },
+    );
+
+    Ok(())
+}
+
+/// Swaps in the pending upgrade's code once its delay has elapsed.
+fn perform_upgrade(env: &Env) -> Result<(), Error> {
+    let pending = get_pending_upgrade(env)?;
+    if env.ledger().timestamp() < pending.eta {
+        return Err(Error::TimelockNotReady);
+    }
+    env.storage().instance().remove(&DataKey::PendingUpgrade);
+
+    publish(
+        env,
+        ContractUpgraded {
+            wasm_hash: pending.wasm_hash.clone(),
+            version: get_contract_version(env),
+        },
+    );
+
+    env.deployer()
+        .update_current_contract_wasm(pending.wasm_hash);
+    Ok(())
+}
+
+/// Storage layout version of the stored data. Deployments predating versioning are version 1.
+fn get_contract_version(env: &Env) -> u32 {
+    env.storage().instance().get(&DataKey::Version).unwrap_or(1)
+}
+
+fn get_migration_progress(env: &Env) -> Option<MigrationProgress> {
+    env.storage().instance().get(&DataKey::Migration)
+}
+
+/// Fails until stored data has been migrated to the current layout. Escrows not
+/// yet migrated are invisible to this code and their funds missing from the
+/// liabilities, so escrow operations and fund movements behave as paused.
+fn ensure_migrated(env: &Env) -> Result<(), Error> {
+    if get_contract_version(env) < CONTRACT_VERSION {
         return Err(Error::ContractPaused);
     }
     Ok(())
 }
 
-fn admin_storage_key() -> Symbol {
-    symbol_short!("admin")
+/// Reads the version 1 configuration, where roles lived in persistent storage
+/// and treasury settings in separate instance keys. Deployments that never set
+/// a treasury fall back to the admin, who can transfer the role afterwards.
+fn load_legacy_config(env: &Env) -> Result<Config, Error> {
+    let admin: Address =
+        get_persistent(env, &symbol_short!("admin")).ok_or(Error::AdminNotInitialized)?;
+    let operator: Address =
+        get_persistent(env, &Symbol::new(env, "operator")).ok_or(Error::OperatorNotInitialized)?;
+    let arbitrator: Address = get_persistent(env, &Symbol::new(env, "arbitrator"))
+        .ok_or(Error::ArbitratorNotInitialized)?;
+    let treasury: Address = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("treasury"))
+        .unwrap_or_else(|| admin.clone());
+    let fee_bps: i128 = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("fee_bps"))
+        .unwrap_or(DEFAULT_FEE_BPS);
+
+    Ok(Config {
+        admin,
+        operator,
+        arbitrator,
+        treasury,
+        fee_bps,
+        fee_change_delay: DEFAULT_FEE_CHANGE_DELAY,
+        governance_delay: DEFAULT_GOVERNANCE_DELAY,
+        emergency_exit_delay: DEFAULT_EMERGENCY_EXIT_DELAY,
+        retention_period: DEFAULT_RETENTION_PERIOD,
+        credit_mode: false,
+        screening: ScreeningSource::Blocklist,
+    })
 }
 
-fn get_admin(env: &Env) -> Result<Address, Error> {
+fn remove_legacy_config(env: &Env) {
+    env.storage().persistent().remove(&symbol_short!("admin"));
+    env.storage()
+        .persistent()
+        .remove(&Symbol::new(env, "operator"));
     env.storage()
         .persistent()
-        .get(&admin_storage_key())
+        .remove(&Symbol::new(env, "arbitrator"));
+    env.storage().instance().remove(&symbol_short!("treasury"));
+    env.storage().instance().remove(&symbol_short!("fee_bps"));
+}
+
+fn get_pending_upgrade(env: &Env) -> Result<PendingUpgrade, Error> {
+    env.storage()
+        .instance()
+        .get(&DataKey::PendingUpgrade)
+        .ok_or(Error::ProposalNotFound)
+}
+
+/// Rewrites a version 1 escrow in the current layout and records it in the
+/// liabilities, indexes, status counts and settlement times it predates.
+/// Returns whether a version 1 escrow was found under `escrow_id`.
+fn migrate_escrow_v1(env: &Env, escrow_id: u64) -> Result<bool, Error> {
+    let legacy = match get_legacy_escrow(env, escrow_id) {
+        Some(legacy) => legacy,
+        None => return Ok(false),
+    };
+    remove_legacy_escrow(env, escrow_id, &legacy.token_address);
+
+    // Version 1 resolved fees at settlement time; snapshot what it would have charged
+    let fee_bps = resolve_fee(
+        env,
+        escrow_id,
+        &legacy.token_address,
+        &legacy.depositor,
+        &legacy.recipient,
+    )?;
+
+    let mut released_count = 0u32;
+    for (index, milestone) in legacy.milestones.iter().enumerate() {
+        if milestone.status == MilestoneStatus::Released {
+            released_count += 1;
+        }
+        save_milestone(env, escrow_id, index as u32, &milestone);
+    }
+    let escrow = Escrow {
+        depositor: legacy.depositor,
+        recipient: legacy.recipient,
+        token_address: legacy.token_address,
+        total_amount: legacy.total_amount,
+        total_released: legacy.total_released,
+        milestone_count: legacy.milestones.len(),
+        released_count,
+        status: legacy.status,
+        deadline: legacy.deadline,
+        resolution: legacy.resolution,
+        fee_payer: FeePayer::Recipient,
+        fee_reserve: 0,
+        fee_bps,
+    };
+    save_escrow(env, escrow_id, &escrow);
+
+    if escrow.status == EscrowStatus::Active || escrow.status == EscrowStatus::Disputed {
+        let held = escrow
+            .total_amount
+            .checked_sub(escrow.total_released)
+            .ok_or(Error::InvalidMilestoneAmount)?;
+        add_liabilities(env, &escrow.token_address, held)?;
+    }
+    index_escrow(env, escrow_id, &escrow.depositor, &escrow.recipient);
+    record_status_change(env, None, escrow.status);
+    // Settled escrows start their retention period at migration time
+    if is_terminal(escrow.status) {
+        record_settlement(env, escrow_id);
+    }
+    Ok(true)
+}
+
+fn get_admin(env: &Env) -> Result<Address, Error> {
+    get_stored_config(env)
+        .map(|config| config.admin)
         .ok_or(Error::AdminNotInitialized)
 }
 
 fn validate_milestones(milestones: &Vec<Milestone>) -> Result<i128, Error> {
-    if milestones.len() > 20 {
+    if milestones.len() > MAX_MILESTONES_PER_CALL {
         return Err(Error::VectorTooLarge);
     }
     let mut total: i128 = 0;
@@ -1203,13 +2752,122 @@ fn validate_milestones(milestones: &Vec<Milestone>) -> Result<i128, Error> {
     Ok(total)
 }
 
-fn verify_all_released(milestones: &Vec<Milestone>) -> bool {
-    for milestone in milestones.iter() {
-        if milestone.status != MilestoneStatus::Released {
-            return false;
+/// Returns the milestone at `milestone_index` if the escrow is active and it can be released.
+fn get_releasable_milestone(
+    env: &Env,
+    escrow: &Escrow,
+    escrow_id: u64,
+    milestone_index: u32,
+) -> Result<Milestone, Error> {
+    if escrow.status != EscrowStatus::Active {
+        return Err(Error::EscrowNotActive);
+    }
+
+    let milestone = load_milestone(env, escrow, escrow_id, milestone_index)?;
+    if milestone.status == MilestoneStatus::Released {
+        return Err(Error::MilestoneAlreadyReleased);
+    }
+    Ok(milestone)
+}
+
+/// Ensures an escrow can be cancelled: not yet settled and nothing released.
+fn ensure_cancellable(escrow: &Escrow) -> Result<(), Error> {
+    if escrow.status != EscrowStatus::Active && escrow.status != EscrowStatus::Created {
+        return Err(Error::InvalidEscrowStatus);
+    }
+    if escrow.total_released > 0 {
+        return Err(Error::MilestoneAlreadyReleased);
+    }
+    Ok(())
+}
+
+fn get_allowed_tokens(env: &Env) -> Vec<Address> {
+    env.storage()
+        .instance()
+        .get(&DataKey::AllowedTokens)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Fails unless `limits` form a non-empty range of non-negative amounts.
+fn validate_token_limits(limits: &TokenLimits) -> Result<(), Error> {
+    if limits.min_amount < 0 || limits.max_amount <= 0 || limits.min_amount > limits.max_amount {
+        return Err(Error::AmountOutOfRange);
+    }
+    Ok(())
+}
+
+fn apply_token_limits(env: &Env, token_address: Address, limits: TokenLimits) -> Result<(), Error> {
+    validate_token_limits(&limits)?;
+
+    let key = DataKey::TokenLimits(token_address.clone());
+    if !env.storage().persistent().has(&key) {
+        let mut tokens = get_allowed_tokens(env);
+        if tokens.len() >= MAX_ALLOWED_TOKENS {
+            return Err(Error::VectorTooLarge);
         }
+        tokens.push_back(token_address.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::AllowedTokens, &tokens);
+    }
+    set_persistent(env, &key, &limits);
+
+    publish_for(
+        env,
+        token_address,
+        TokenAllowed {
+            min_amount: limits.min_amount,
+            max_amount: limits.max_amount,
+        },
+    );
+
+    Ok(())
+}
+
+fn apply_token_removal(env: &Env, token_address: Address, actor: Address) -> Result<(), Error> {
+    let key = DataKey::TokenLimits(token_address.clone());
+    if !env.storage().persistent().has(&key) {
+        return Err(Error::TokenNotAllowed);
+    }
+    env.storage().persistent().remove(&key);
+
+    let mut tokens = get_allowed_tokens(env);
+    if let Some(index) = tokens.first_index_of(&token_address) {
+        tokens.remove(index);
+    }
+    env.storage()
+        .instance()
+        .set(&DataKey::AllowedTokens, &tokens);
+
+    publish_for(env, token_address, TokenRemoved { removed_by: actor });
+
+    Ok(())
+}
+
+/// Fails unless `token_address` is allowlisted and `total_amount` is within its limits.
+fn ensure_token_allowed(
+    env: &Env,
+    token_address: &Address,
+    total_amount: i128,
+) -> Result<(), Error> {
+    let limits: TokenLimits = get_persistent(env, &DataKey::TokenLimits(token_address.clone()))
+        .ok_or(Error::TokenNotAllowed)?;
+    if total_amount < limits.min_amount || total_amount > limits.max_amount {
+        return Err(Error::AmountOutOfRange);
+    }
+    Ok(())
+}
+
+/// Returns the unreleased balance of an escrow, failing if there is nothing to refund.
+fn get_refundable_balance(escrow: &Escrow) -> Result<i128, Error> {
+    let remaining_balance = escrow
+        .total_amount
+        .checked_sub(escrow.total_released)
+        .ok_or(Error::InvalidMilestoneAmount)?;
+    if remaining_balance <= 0 {
+        return Err(Error::NoFundsToRefund);
     }
-    true
+    Ok(remaining_balance)
 }
 
 /// Calculate platform fee using basis points (BPS)
@@ -1229,21 +2887,119 @@ fn calculate_fee(amount: i128, fee_bps: i128) -> Result<i128, Error> {
     Ok(fee)
 }
 
+fn role_symbol(env: &Env, role: Role) -> Symbol {
+    match role {
+        Role::Admin => Symbol::new(env, "Admin"),
+        Role::Operator => Symbol::new(env, "Operator"),
+        Role::Arbitrator => Symbol::new(env, "Arbitrator"),
+        Role::Treasury => Symbol::new(env, "Treasury"),
+    }
+}
+
+/// Records `new_addr` as the pending holder of `role`, replacing any pending transfer.
+fn apply_role_transfer_proposal(env: &Env, role: Role, new_addr: Address) -> Result<(), Error> {
+    let current = get_role_holder(env, role)?;
+    set_persistent(env, &DataKey::PendingRoleTransfer(role), &new_addr);
+
+    publish_for(
+        env,
+        role_symbol(env, role),
+        RoleTransferProposed {
+            current,
+            proposed: new_addr,
+        },
+    );
+
+    Ok(())
+}
+
+fn apply_role_transfer_cancel(env: &Env, role: Role) -> Result<(), Error> {
+    let pending_key = DataKey::PendingRoleTransfer(role);
+    let new_addr: Address =
+        get_persistent(env, &pending_key).ok_or(Error::NoPendingRoleTransfer)?;
+    env.storage().persistent().remove(&pending_key);
+
+    publish_for(
+        env,
+        role_symbol(env, role),
+        RoleTransferCancelled { proposed: new_addr },
+    );
+
+    Ok(())
+}
+
+fn get_role_holder(env: &Env, role: Role) -> Result<Address, Error> {
+    match role {
+        Role::Admin => get_admin(env),
+        Role::Operator => get_operator(env),
+        Role::Arbitrator => get_arbitrator(env),
+        Role::Treasury => get_treasury(env),
+    }
+}
+
+fn set_role_holder(env: &Env, role: Role, addr: &Address) -> Result<(), Error> {
+    let mut config = get_stored_config(env).ok_or(Error::AdminNotInitialized)?;
+    let holder = match role {
+        Role::Admin => &mut config.admin,
+        Role::Operator => &mut config.operator,
+        Role::Arbitrator => &mut config.arbitrator,
+        Role::Treasury => &mut config.treasury,
+    };
+    *holder = addr.clone();
+    set_stored_config(env, &config);
+    Ok(())
+}
+
+fn get_stored_config(env: &Env) -> Option<Config> {
+    let config = env.storage().instance().get(&DataKey::Config);
+    if config.is_some() {
+        extend_instance_ttl(env);
+    }
+    config
+}
+
+fn set_stored_config(env: &Env, config: &Config) {
+    env.storage().instance().set(&DataKey::Config, config);
+    extend_instance_ttl(env);
+}
+
 fn get_operator(env: &Env) -> Result<Address, Error> {
-    env.storage()
-        .persistent()
-        .get(&Symbol::new(env, "operator"))
+    get_stored_config(env)
+        .map(|config| config.operator)
         .ok_or(Error::OperatorNotInitialized)
 }
 
 fn get_arbitrator(env: &Env) -> Result<Address, Error> {
-    env.storage()
-        .persistent()
-        .get(&Symbol::new(env, "arbitrator"))
+    get_stored_config(env)
+        .map(|config| config.arbitrator)
         .ok_or(Error::ArbitratorNotInitialized)
 }
 
+fn get_treasury(env: &Env) -> Result<Address, Error> {
+    get_stored_config(env)
+        .map(|config| config.treasury)
+        .ok_or(Error::TreasuryNotInitialized)
+}
+
+#[cfg(test)]
+mod archive_tests;
+#[cfg(test)]
+mod credits_tests;
 #[cfg(test)]
 mod fee_tests;
 #[cfg(test)]
+mod governance_tests;
+#[cfg(test)]
+mod index_tests;
+#[cfg(test)]
+mod multisig_tests;
+#[cfg(test)]
+mod screening_tests;
+#[cfg(test)]
+mod stats_tests;
+#[cfg(test)]
 mod test;
+#[cfg(test)]
+mod test_fixtures;
+#[cfg(test)]
+mod upgrade_test;
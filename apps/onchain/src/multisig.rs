@@ -0,0 +1,282 @@
+// multisig.rs
+//! M-of-n approval for privileged operations.
+//!
+//! Once enabled, every single-key admin entrypoint is disabled. Signers instead
+//! propose an `AdminAction`, collect approvals until the threshold is met, and
+//! anyone may then execute it before the proposal expires.
+#[cfg(test)]
+use crate::__VaultixEscrow_fn_set_registry;
+use crate::events::{
+    publish, publish_for, ActionApproved, ActionExecuted, ActionProposed, MultisigUpdated,
+};
+use crate::{
+    get_admin, get_persistent, governance::submit_action, perform_upgrade, set_persistent, DataKey,
+    Error, FeeDiscount, PausableOperation, Role, TokenLimits, VaultixEscrow, VaultixEscrowClient,
+};
+use soroban_sdk::{contractimpl, contracttype, Address, BytesN, Env, Vec};
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultisigConfig {
+    pub signers: Vec<Address>,
+    pub threshold: u32,
+    pub proposal_ttl: u64, // Seconds a proposal stays executable after creation
+}
+
+/// Privileged operations. Once multisig is enabled, they all require multisig
+/// approval; configuration changes among them also pass through the governance
+/// queue.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AdminAction {
+    SetPaused(bool),
+    SetOperationPaused(PausableOperation, bool),
+    SetTokenPaused(Address, bool),
+    UpdateFee(i128),
+    SetTokenFee(Address, i128),
+    SetEscrowFee(u64, i128),
+    ProposeUpgrade(BytesN<32>),
+    CancelUpgrade,
+    ExecuteUpgrade,
+    SetMultisig(MultisigConfig),
+    EnableMultisig(MultisigConfig),
+    SweepExcess(Address, Address),
+    SetFeeChangeDelay(u64),
+    GrantRole(Role, Address),
+    RevokeRole(Role, Address),
+    SetRoleAdmin(Role, Role),
+    ProposeRoleTransfer(Role, Address),
+    SetGovernanceDelay(u64),
+    CancelQueuedChange(u64),
+    CancelRoleTransfer(Role),
+    SetEmergencyExitDelay(u64),
+    AddFeeDiscount(Address, FeeDiscount),
+    RemoveFeeDiscount(Address),
+    AllowToken(Address, TokenLimits),
+    RemoveAllowedToken(Address),
+    SetScreeningContract(Address),
+    ClearScreeningContract,
+    SetAddressBlocked(Address, bool),
+    SetRetentionPeriod(u64),
+    SetCreditMode(bool),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultisigProposal {
+    pub action: AdminAction,
+    pub proposer: Address,
+    pub approvals: Vec<Address>,
+    pub expires_at: u64,
+    pub executed: bool,
+}
+
+#[contractimpl]
+impl VaultixEscrow {
+    /// Enable multisig administration for privileged operations.
+    /// Only admin can call, and only while multisig is not yet enabled; afterwards
+    /// the signer set can only change through an approved `AdminAction::SetMultisig`.
+    /// Enabling is queued, so signers can be reviewed before they take over.
+    pub fn enable_multisig(env: Env, config: MultisigConfig) -> Result<(), Error> {
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+        ensure_multisig_not_enabled(&env)?;
+
+        submit_action(&env, AdminAction::EnableMultisig(config), admin)
+    }
+
+    pub fn get_multisig(env: Env) -> Option<MultisigConfig> {
+        get_multisig_config(&env)
+    }
+
+    /// Propose a privileged action. The proposer must be a signer and counts as
+    /// its first approval.
+    ///
+    /// # Returns
+    /// The ID of the new proposal
+    pub fn propose_action(env: Env, proposer: Address, action: AdminAction) -> Result<u64, Error> {
+        proposer.require_auth();
+        let config = get_multisig_config(&env).ok_or(Error::MultisigNotConfigured)?;
+        if !config.signers.contains(&proposer) {
+            return Err(Error::NotASigner);
+        }
+
+        let proposal_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MultisigCounter)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::MultisigCounter, &(proposal_id + 1));
+
+        let expires_at = env
+            .ledger()
+            .timestamp()
+            .checked_add(config.proposal_ttl)
+            .ok_or(Error::InvalidMultisigConfig)?;
+        let mut approvals = Vec::new(&env);
+        approvals.push_back(proposer.clone());
+        let proposal = MultisigProposal {
+            action,
+            proposer: proposer.clone(),
+            approvals,
+            expires_at,
+            executed: false,
+        };
+        set_persistent(&env, &DataKey::MultisigProposal(proposal_id), &proposal);
+
+        publish_for(
+            &env,
+            proposal_id,
+            ActionProposed {
+                proposer,
+                action: proposal.action,
+                expires_at,
+            },
+        );
+
+        Ok(proposal_id)
+    }
+
+    /// Approve a pending proposal. The approver must be a signer.
+    pub fn approve_action(env: Env, signer: Address, proposal_id: u64) -> Result<(), Error> {
+        signer.require_auth();
+        let config = get_multisig_config(&env).ok_or(Error::MultisigNotConfigured)?;
+        if !config.signers.contains(&signer) {
+            return Err(Error::NotASigner);
+        }
+
+        let mut proposal = load_open_proposal(&env, proposal_id)?;
+        if proposal.approvals.contains(&signer) {
+            return Err(Error::AlreadyApproved);
+        }
+        proposal.approvals.push_back(signer.clone());
+        set_persistent(&env, &DataKey::MultisigProposal(proposal_id), &proposal);
+
+        publish_for(
+            &env,
+            proposal_id,
+            ActionApproved {
+                signer,
+                approvals: proposal.approvals.len(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Execute a proposal whose approvals meet the threshold.
+    /// Anyone can call this function before the proposal expires.
+    ///
+    /// Approvals are counted against the current signer set, so approvals from
+    /// signers removed after approving no longer count.
+    pub fn execute_action(env: Env, proposal_id: u64) -> Result<(), Error> {
+        let config = get_multisig_config(&env).ok_or(Error::MultisigNotConfigured)?;
+        let mut proposal = load_open_proposal(&env, proposal_id)?;
+
+        let mut approvals = 0u32;
+        for approver in proposal.approvals.iter() {
+            if config.signers.contains(&approver) {
+                approvals += 1;
+            }
+        }
+        if approvals < config.threshold {
+            return Err(Error::ThresholdNotMet);
+        }
+
+        proposal.executed = true;
+        set_persistent(&env, &DataKey::MultisigProposal(proposal_id), &proposal);
+
+        match proposal.action.clone() {
+            AdminAction::ExecuteUpgrade => {
+                // Emit before the code swap, matching the single-key upgrade path
+                publish_executed(&env, proposal_id, AdminAction::ExecuteUpgrade);
+                return perform_upgrade(&env);
+            }
+            // Configuration changes still wait out the governance delay
+            action => submit_action(&env, action, env.current_contract_address())?,
+        }
+
+        publish_executed(&env, proposal_id, proposal.action);
+        Ok(())
+    }
+
+    pub fn get_action_proposal(env: Env, proposal_id: u64) -> Result<MultisigProposal, Error> {
+        get_persistent(&env, &DataKey::MultisigProposal(proposal_id)).ok_or(Error::ProposalNotFound)
+    }
+}
+
+fn get_multisig_config(env: &Env) -> Option<MultisigConfig> {
+    env.storage().instance().get(&DataKey::Multisig)
+}
+
+/// Fails unless `config` lists distinct signers that can meet its threshold.
+pub(crate) fn validate_multisig_config(config: &MultisigConfig) -> Result<(), Error> {
+    if config.threshold == 0 || config.threshold > config.signers.len() || config.proposal_ttl == 0
+    {
+        return Err(Error::InvalidMultisigConfig);
+    }
+    for (i, signer) in config.signers.iter().enumerate() {
+        if config.signers.first_index_of(&signer) != Some(i as u32) {
+            return Err(Error::InvalidMultisigConfig);
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn set_multisig_config(env: &Env, config: &MultisigConfig) -> Result<(), Error> {
+    validate_multisig_config(config)?;
+
+    env.storage().instance().set(&DataKey::Multisig, config);
+
+    publish(
+        env,
+        MultisigUpdated {
+            signers: config.signers.clone(),
+            threshold: config.threshold,
+            proposal_ttl: config.proposal_ttl,
+        },
+    );
+
+    Ok(())
+}
+
+/// Loads a proposal that has neither been executed nor expired.
+fn load_open_proposal(env: &Env, proposal_id: u64) -> Result<MultisigProposal, Error> {
+    let proposal: MultisigProposal = get_persistent(env, &DataKey::MultisigProposal(proposal_id))
+        .ok_or(Error::ProposalNotFound)?;
+    if proposal.executed {
+        return Err(Error::ProposalAlreadyExecuted);
+    }
+    if env.ledger().timestamp() > proposal.expires_at {
+        return Err(Error::ProposalExpired);
+    }
+    Ok(proposal)
+}
+
+fn publish_executed(env: &Env, proposal_id: u64, action: AdminAction) {
+    publish_for(env, proposal_id, ActionExecuted { action });
+}
+
+/// Installs the first signer set. Fails if multisig was enabled while the
+/// change was queued, so a second queued enable cannot replace the signers.
+pub(crate) fn apply_enable_multisig(env: &Env, config: &MultisigConfig) -> Result<(), Error> {
+    ensure_multisig_not_enabled(env)?;
+    set_multisig_config(env, config)
+}
+
+fn ensure_multisig_not_enabled(env: &Env) -> Result<(), Error> {
+    if get_multisig_config(env).is_some() {
+        return Err(Error::AlreadyInitialized);
+    }
+    Ok(())
+}
+
+/// Fails when multisig is enabled, disabling the single-key path for privileged actions.
+pub(crate) fn ensure_multisig_disabled(env: &Env) -> Result<(), Error> {
+    if get_multisig_config(env).is_some() {
+        return Err(Error::MultisigRequired);
+    }
+    Ok(())
+}
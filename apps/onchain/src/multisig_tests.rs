@@ -0,0 +1,199 @@
+use crate::test_fixtures::{execute_queued, register_contract};
+use crate::{AdminAction, Error, MultisigConfig, Role, VaultixEscrowClient, MIN_GOVERNANCE_DELAY};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Env,
+};
+
+/// Helper function to register the contract and enable a 2-of-3 multisig
+fn setup_multisig(env: &Env) -> (VaultixEscrowClient<'_>, Address, Address, Address) {
+    let client = register_contract(env, Some(50), MIN_GOVERNANCE_DELAY);
+
+    let signer_a = Address::generate(env);
+    let signer_b = Address::generate(env);
+    let signer_c = Address::generate(env);
+    client.enable_multisig(&MultisigConfig {
+        signers: vec![env, signer_a.clone(), signer_b.clone(), signer_c.clone()],
+        threshold: 2,
+        proposal_ttl: 3_600,
+    });
+    execute_queued(env, &client);
+
+    (client, signer_a, signer_b, signer_c)
+}
+
+#[test]
+fn test_multisig_executes_after_threshold() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, signer_a, signer_b, _) = setup_multisig(&env);
+    let operator = client.get_role(&Role::Operator);
+
+    // Single-key path is disabled once multisig is enabled
+    let result = client.try_update_fee(&operator, &100);
+    assert_eq!(result, Err(Ok(Error::MultisigRequired)));
+
+    let proposal_id = client.propose_action(&signer_a, &AdminAction::UpdateFee(100));
+    let result = client.try_execute_action(&proposal_id);
+    assert_eq!(result, Err(Ok(Error::ThresholdNotMet)));
+
+    let result = client.try_approve_action(&signer_a, &proposal_id);
+    assert_eq!(result, Err(Ok(Error::AlreadyApproved)));
+
+    client.approve_action(&signer_b, &proposal_id);
+    client.execute_action(&proposal_id);
+    execute_queued(&env, &client);
+
+    let (_, fee_bps) = client.get_config();
+    assert_eq!(fee_bps, 100);
+    assert!(client.get_action_proposal(&proposal_id).executed);
+
+    let result = client.try_execute_action(&proposal_id);
+    assert_eq!(result, Err(Ok(Error::ProposalAlreadyExecuted)));
+}
+
+#[test]
+fn test_multisig_rejects_outsiders_and_expired_proposals() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, signer_a, _, signer_c) = setup_multisig(&env);
+
+    let outsider = Address::generate(&env);
+    let result = client.try_propose_action(&outsider, &AdminAction::SetPaused(true));
+    assert_eq!(result, Err(Ok(Error::NotASigner)));
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let proposal_id = client.propose_action(&signer_a, &AdminAction::SetPaused(true));
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000 + 3_601);
+    let result = client.try_approve_action(&signer_c, &proposal_id);
+    assert_eq!(result, Err(Ok(Error::ProposalExpired)));
+}
+
+#[test]
+fn test_multisig_rotates_signer_set() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, signer_a, signer_b, _) = setup_multisig(&env);
+
+    // A threshold above the signer count is rejected before it is queued
+    let invalid = MultisigConfig {
+        signers: vec![&env, signer_a.clone()],
+        threshold: 2,
+        proposal_ttl: 3_600,
+    };
+    let proposal_id = client.propose_action(&signer_a, &AdminAction::SetMultisig(invalid));
+    client.approve_action(&signer_b, &proposal_id);
+    let result = client.try_execute_action(&proposal_id);
+    assert_eq!(result, Err(Ok(Error::InvalidMultisigConfig)));
+
+    let rotated = MultisigConfig {
+        signers: vec![&env, signer_a.clone(), signer_b.clone()],
+        threshold: 2,
+        proposal_ttl: 7_200,
+    };
+    let proposal_id = client.propose_action(&signer_a, &AdminAction::SetMultisig(rotated.clone()));
+    client.approve_action(&signer_b, &proposal_id);
+    client.execute_action(&proposal_id);
+    execute_queued(&env, &client);
+
+    assert_eq!(client.get_multisig(), Some(rotated));
+}
+
+#[test]
+fn test_multisig_disables_every_admin_entrypoint() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, signer_a, _, _) = setup_multisig(&env);
+    let admin = client.get_role(&Role::Admin);
+
+    let result = client.try_enable_multisig(&MultisigConfig {
+        signers: vec![&env, signer_a],
+        threshold: 1,
+        proposal_ttl: 3_600,
+    });
+    assert_eq!(result, Err(Ok(Error::AlreadyInitialized)));
+
+    let result = client.try_set_governance_delay(&0);
+    assert_eq!(result, Err(Ok(Error::MultisigRequired)));
+    let result = client.try_set_fee_change_delay(&0);
+    assert_eq!(result, Err(Ok(Error::MultisigRequired)));
+    let result = client.try_set_emergency_exit_delay(&0);
+    assert_eq!(result, Err(Ok(Error::MultisigRequired)));
+    let result = client.try_set_retention_period(&0);
+    assert_eq!(result, Err(Ok(Error::MultisigRequired)));
+    let result = client.try_set_credit_mode(&true);
+    assert_eq!(result, Err(Ok(Error::MultisigRequired)));
+    let result = client.try_set_screening_contract(&None);
+    assert_eq!(result, Err(Ok(Error::MultisigRequired)));
+    let result = client.try_propose_role_transfer(&Role::Operator, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(Error::MultisigRequired)));
+    let result = client.try_cancel_role_transfer(&Role::Operator);
+    assert_eq!(result, Err(Ok(Error::MultisigRequired)));
+    let result = client.try_grant_role(&admin, &Role::Operator, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(Error::MultisigRequired)));
+    let result = client.try_revoke_role(&admin, &Role::Operator, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(Error::MultisigRequired)));
+    let result = client.try_set_role_admin(&Role::Operator, &Role::Admin);
+    assert_eq!(result, Err(Ok(Error::MultisigRequired)));
+    let result = client.try_cancel_queued_change(&0);
+    assert_eq!(result, Err(Ok(Error::MultisigRequired)));
+}
+
+#[test]
+fn test_multisig_cancels_queued_change() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, signer_a, signer_b, _) = setup_multisig(&env);
+
+    let proposal_id = client.propose_action(&signer_a, &AdminAction::UpdateFee(100));
+    client.approve_action(&signer_b, &proposal_id);
+    client.execute_action(&proposal_id);
+    assert_eq!(client.get_queued_changes(), vec![&env, 1u64]);
+
+    // Cancellation needs the same approvals and applies without waiting
+    let proposal_id = client.propose_action(&signer_a, &AdminAction::CancelQueuedChange(1));
+    let result = client.try_execute_action(&proposal_id);
+    assert_eq!(result, Err(Ok(Error::ThresholdNotMet)));
+    client.approve_action(&signer_b, &proposal_id);
+    client.execute_action(&proposal_id);
+
+    assert_eq!(client.get_queued_changes().len(), 0);
+    env.ledger()
+        .with_mut(|li| li.timestamp += MIN_GOVERNANCE_DELAY);
+    let result = client.try_execute_queued_change(&1);
+    assert_eq!(result, Err(Ok(Error::ProposalNotFound)));
+    assert_eq!(client.get_config().1, 50);
+}
+
+#[test]
+fn test_enabling_multisig_is_queued_and_cancellable() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = register_contract(&env, Some(50), MIN_GOVERNANCE_DELAY);
+    let operator = client.get_role(&Role::Operator);
+
+    let config = MultisigConfig {
+        signers: vec![&env, Address::generate(&env), Address::generate(&env)],
+        threshold: 2,
+        proposal_ttl: 3_600,
+    };
+    for _ in 0..3 {
+        client.enable_multisig(&config);
+    }
+    assert_eq!(client.get_multisig(), None);
+    assert_eq!(client.get_queued_changes(), vec![&env, 0u64, 1, 2]);
+
+    // Single-key entrypoints keep working while the change waits
+    client.set_paused(&operator, &true);
+    client.cancel_queued_change(&0);
+
+    env.ledger()
+        .with_mut(|li| li.timestamp += MIN_GOVERNANCE_DELAY);
+    client.execute_queued_change(&1);
+    assert_eq!(client.get_multisig(), Some(config));
+
+    // A second queued enable cannot replace the installed signer set
+    let result = client.try_execute_queued_change(&2);
+    assert_eq!(result, Err(Ok(Error::AlreadyInitialized)));
+}
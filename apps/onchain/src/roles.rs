@@ -0,0 +1,150 @@
+// roles.rs
+//! Role registry allowing several holders of the operator and arbitrator roles.
+//!
+//! The primary holder of every role lives in `Config` and changes through the
+//! two-step transfer. Operators and arbitrators can additionally be granted to
+//! any number of addresses by holders of the role's admin role.
+#[cfg(test)]
+use crate::__VaultixEscrow_fn_set_registry;
+use crate::events::{publish_for, RoleAdminChanged, RoleGranted, RoleRevoked};
+use crate::{
+    get_persistent, get_role_holder, governance::submit_action, multisig::ensure_multisig_disabled,
+    role_symbol, set_persistent, AdminAction, DataKey, Error, Role, VaultixEscrow,
+    VaultixEscrowClient,
+};
+use soroban_sdk::{contractimpl, Address, Env};
+
+#[contractimpl]
+impl VaultixEscrow {
+    /// Grant `role` to `account` in addition to its current holders.
+    /// `caller` must hold the role's admin role. Only the operator and arbitrator
+    /// roles can have several holders, so granting any other role fails with
+    /// `RoleNotDelegable`. Granting an existing holder is a no-op.
+    pub fn grant_role(
+        env: Env,
+        caller: Address,
+        role: Role,
+        account: Address,
+    ) -> Result<(), Error> {
+        require_role(&env, get_role_admin(&env, role), &caller)?;
+        ensure_multisig_disabled(&env)?;
+        if !is_delegable(role) {
+            return Err(Error::RoleNotDelegable);
+        }
+
+        submit_action(&env, AdminAction::GrantRole(role, account), caller)
+    }
+
+    /// Revoke a grant of `role` from `account`. The primary holder is unaffected
+    /// and can only be replaced through a role transfer.
+    /// `caller` must hold the role's admin role. Like pausing, revocation skips
+    /// the governance queue and applies immediately.
+    pub fn revoke_role(
+        env: Env,
+        caller: Address,
+        role: Role,
+        account: Address,
+    ) -> Result<(), Error> {
+        require_role(&env, get_role_admin(&env, role), &caller)?;
+        ensure_multisig_disabled(&env)?;
+
+        submit_action(&env, AdminAction::RevokeRole(role, account), caller)
+    }
+
+    pub fn has_role(env: Env, role: Role, account: Address) -> bool {
+        has_role(&env, role, &account).unwrap_or(false)
+    }
+
+    /// Set the role whose holders may grant and revoke `role`.
+    /// Only admin can call.
+    pub fn set_role_admin(env: Env, role: Role, admin_role: Role) -> Result<(), Error> {
+        let admin = get_role_holder(&env, Role::Admin)?;
+        admin.require_auth();
+        ensure_multisig_disabled(&env)?;
+
+        submit_action(&env, AdminAction::SetRoleAdmin(role, admin_role), admin)
+    }
+
+    pub fn get_role_admin(env: Env, role: Role) -> Role {
+        get_role_admin(&env, role)
+    }
+}
+
+fn get_role_admin(env: &Env, role: Role) -> Role {
+    env.storage()
+        .instance()
+        .get(&DataKey::RoleAdmin(role))
+        .unwrap_or(Role::Admin)
+}
+
+fn is_delegable(role: Role) -> bool {
+    matches!(role, Role::Operator | Role::Arbitrator)
+}
+
+/// Grants `role` to `account`; granting an existing holder is a no-op.
+pub(crate) fn apply_grant_role(
+    env: &Env,
+    role: Role,
+    account: Address,
+    actor: Address,
+) -> Result<(), Error> {
+    if !is_delegable(role) {
+        return Err(Error::RoleNotDelegable);
+    }
+
+    let key = DataKey::RoleMember(role, account.clone());
+    if get_persistent::<_, bool>(env, &key).is_some() {
+        return Ok(());
+    }
+    set_persistent(env, &key, &true);
+
+    publish_for(env, role_symbol(env, role), RoleGranted { account, actor });
+
+    Ok(())
+}
+
+/// Revokes a grant of `role` from `account`; revoking a non-member is a no-op.
+pub(crate) fn apply_revoke_role(env: &Env, role: Role, account: Address, actor: Address) {
+    let key = DataKey::RoleMember(role, account.clone());
+    if !env.storage().persistent().has(&key) {
+        return;
+    }
+    env.storage().persistent().remove(&key);
+
+    publish_for(env, role_symbol(env, role), RoleRevoked { account, actor });
+}
+
+pub(crate) fn apply_role_admin(env: &Env, role: Role, admin_role: Role) {
+    let previous = get_role_admin(env, role);
+    env.storage()
+        .instance()
+        .set(&DataKey::RoleAdmin(role), &admin_role);
+
+    publish_for(
+        env,
+        role_symbol(env, role),
+        RoleAdminChanged {
+            previous_admin_role: previous,
+            new_admin_role: admin_role,
+        },
+    );
+}
+
+/// Whether `account` is the primary holder of `role` or has been granted it.
+/// Fails when the role has not been initialized.
+fn has_role(env: &Env, role: Role, account: &Address) -> Result<bool, Error> {
+    if get_role_holder(env, role)? == *account {
+        return Ok(true);
+    }
+    Ok(is_delegable(role)
+        && get_persistent::<_, bool>(env, &DataKey::RoleMember(role, account.clone())).is_some())
+}
+
+/// Requires `caller` to authorize the call and to hold `role`.
+pub(crate) fn require_role(env: &Env, role: Role, caller: &Address) -> Result<(), Error> {
+    caller.require_auth();
+    if !has_role(env, role, caller)? {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
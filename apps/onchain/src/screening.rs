@@ -0,0 +1,136 @@
+// screening.rs
+//! Compliance screening of escrow parties and payees.
+//!
+//! Both parties are screened when an escrow is created and the depositor again
+//! when it is funded. Releases to the recipient are screened as well, while
+//! refunds to the depositor never are, so blocking an address cannot strand
+//! funds it deposited.
+//!
+//! The admin may point the contract at an external screening contract exposing
+//! `is_allowed(addr) -> bool`. While none is configured, the operator-managed
+//! local blocklist is consulted instead. A screener that fails or is missing
+//! blocks every screened address, leaving refunds as the way out.
+#[cfg(test)]
+use crate::__VaultixEscrow_fn_set_registry;
+use crate::events::{publish, AddressBlockUpdated, ScreeningContractCleared, ScreeningContractSet};
+use crate::{
+    get_admin, get_persistent, get_stored_config, governance::submit_action,
+    multisig::ensure_multisig_disabled, require_role, set_persistent, set_stored_config,
+    AdminAction, DataKey, Error, Role, VaultixEscrow, VaultixEscrowClient,
+};
+use soroban_sdk::{contractclient, contractimpl, contracttype, Address, Env};
+
+/// Where parties are screened, as recorded in `Config`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ScreeningSource {
+    Blocklist,         // The operator-managed local blocklist
+    Contract(Address), // An external screening contract
+}
+
+/// Interface a screening contract must implement.
+#[contractclient(name = "ScreeningClient")]
+pub trait ScreeningInterface {
+    fn is_allowed(env: Env, addr: Address) -> bool;
+}
+
+#[contractimpl]
+impl VaultixEscrow {
+    /// Set or clear the external screening contract.
+    /// Only admin can call. Clearing it falls back to the local blocklist.
+    /// The new screener is queued and only screens calls made after it executes.
+    pub fn set_screening_contract(env: Env, screener: Option<Address>) -> Result<(), Error> {
+        let admin = get_admin(&env)?;
+        admin.require_auth();
+        ensure_multisig_disabled(&env)?;
+
+        let action = match screener {
+            Some(screener) => AdminAction::SetScreeningContract(screener),
+            None => AdminAction::ClearScreeningContract,
+        };
+        submit_action(&env, action, admin)
+    }
+
+    pub fn get_screening_contract(env: Env) -> Option<Address> {
+        get_screening_contract(&env)
+    }
+
+    /// Add or remove `addr` on the local blocklist.
+    /// Only operators can call. Like pausing, blocking applies immediately;
+    /// unblocking passes through the governance queue.
+    pub fn set_address_blocked(
+        env: Env,
+        caller: Address,
+        addr: Address,
+        blocked: bool,
+    ) -> Result<(), Error> {
+        require_role(&env, Role::Operator, &caller)?;
+        ensure_multisig_disabled(&env)?;
+
+        submit_action(&env, AdminAction::SetAddressBlocked(addr, blocked), caller)
+    }
+
+    /// Whether `addr` passes screening with the currently active screener.
+    pub fn is_address_allowed(env: Env, addr: Address) -> bool {
+        is_screened_allowed(&env, &addr)
+    }
+}
+
+fn get_screening_contract(env: &Env) -> Option<Address> {
+    match get_stored_config(env)?.screening {
+        ScreeningSource::Contract(screener) => Some(screener),
+        ScreeningSource::Blocklist => None,
+    }
+}
+
+fn is_screened_allowed(env: &Env, addr: &Address) -> bool {
+    match get_screening_contract(env) {
+        Some(screener) => matches!(
+            ScreeningClient::new(env, &screener).try_is_allowed(addr),
+            Ok(Ok(true))
+        ),
+        None => get_persistent::<_, bool>(env, &DataKey::Blocked(addr.clone())).is_none(),
+    }
+}
+
+/// Sets the external screening contract, or clears it when `screener` is `None`.
+pub(crate) fn apply_screening_contract(
+    env: &Env,
+    screener: Option<Address>,
+    actor: Address,
+) -> Result<(), Error> {
+    let mut config = get_stored_config(env).ok_or(Error::AdminNotInitialized)?;
+    match screener {
+        Some(screener) => {
+            config.screening = ScreeningSource::Contract(screener.clone());
+            set_stored_config(env, &config);
+            publish(env, ScreeningContractSet { screener });
+        }
+        None => {
+            config.screening = ScreeningSource::Blocklist;
+            set_stored_config(env, &config);
+            publish(env, ScreeningContractCleared { cleared_by: actor });
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn apply_address_blocked(env: &Env, addr: Address, blocked: bool) {
+    let key = DataKey::Blocked(addr.clone());
+    if blocked {
+        set_persistent(env, &key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+
+    publish(env, AddressBlockUpdated { addr, blocked });
+}
+
+/// Fails when `addr` is rejected by the active screener.
+pub(crate) fn ensure_screened(env: &Env, addr: &Address) -> Result<(), Error> {
+    if !is_screened_allowed(env, addr) {
+        return Err(Error::AddressBlocked);
+    }
+    Ok(())
+}
@@ -0,0 +1,152 @@
+use crate::test_fixtures::{create_funded, execute_queued, milestones, setup};
+use crate::{Error, Role};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, symbol_short, testutils::Address as _, token, Address,
+    Env, Vec,
+};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum MockScreeningError {
+    Unavailable = 1,
+}
+
+/// Screening contract stub that rejects a configurable list of addresses, or
+/// fails every call once `break_down` is called
+#[contract]
+pub struct MockScreening;
+
+#[contractimpl]
+impl MockScreening {
+    pub fn deny(env: Env, addr: Address) {
+        let mut denied: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("denied"))
+            .unwrap_or(Vec::new(&env));
+        denied.push_back(addr);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("denied"), &denied);
+    }
+
+    pub fn break_down(env: Env) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("broken"), &true);
+    }
+
+    pub fn is_allowed(env: Env, addr: Address) -> Result<bool, MockScreeningError> {
+        if env.storage().instance().has(&symbol_short!("broken")) {
+            return Err(MockScreeningError::Unavailable);
+        }
+        let denied: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("denied"))
+            .unwrap_or(Vec::new(&env));
+        Ok(!denied.contains(&addr))
+    }
+}
+
+#[test]
+fn test_local_blocklist_blocks_creation_and_payouts() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_address) = setup(&env, Some(0));
+    let operator = client.get_role(&Role::Operator);
+
+    let blocked = Address::generate(&env);
+    client.set_address_blocked(&operator, &blocked, &true);
+    assert!(!client.is_address_allowed(&blocked));
+    let result = client.try_create_escrow(
+        &1u64,
+        &Address::generate(&env),
+        &blocked,
+        &token_address,
+        &milestones(&env, &[10_000]),
+        &1_706_400_000u64,
+    );
+    assert_eq!(result, Err(Ok(Error::AddressBlocked)));
+
+    // An address blocked after funding cannot receive a payout
+    let (depositor, recipient) = create_funded(&env, &client, 1, &token_address, &[10_000]);
+    client.set_address_blocked(&operator, &recipient, &true);
+    let result = client.try_release_milestone(&1u64, &0);
+    assert_eq!(result, Err(Ok(Error::AddressBlocked)));
+
+    // A blocked depositor can still be refunded
+    client.set_address_blocked(&operator, &depositor, &true);
+    client.cancel_escrow(&1u64);
+    assert_eq!(
+        token::Client::new(&env, &token_address).balance(&depositor),
+        10_000
+    );
+}
+
+#[test]
+fn test_external_screening_contract_overrides_blocklist() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_address) = setup(&env, None);
+    let operator = client.get_role(&Role::Operator);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    // The local blocklist is ignored once an external screener is set
+    client.set_address_blocked(&operator, &recipient, &true);
+    let screener_id = env.register_contract(None, MockScreening);
+    let screener = MockScreeningClient::new(&env, &screener_id);
+    screener.deny(&depositor);
+    client.set_screening_contract(&Some(screener_id.clone()));
+    execute_queued(&env, &client);
+    assert_eq!(client.get_screening_contract(), Some(screener_id));
+    assert!(client.is_address_allowed(&recipient));
+
+    let result = client.try_create_escrow(
+        &1u64,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones(&env, &[10_000]),
+        &1_706_400_000u64,
+    );
+    assert_eq!(result, Err(Ok(Error::AddressBlocked)));
+
+    client.set_screening_contract(&None);
+    execute_queued(&env, &client);
+    assert!(!client.is_address_allowed(&recipient));
+}
+
+#[test]
+fn test_failing_screener_blocks_instead_of_trapping() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_address) = setup(&env, Some(0));
+    let (depositor, recipient) = create_funded(&env, &client, 1, &token_address, &[10_000]);
+
+    let screener_id = env.register_contract(None, MockScreening);
+    MockScreeningClient::new(&env, &screener_id).break_down();
+    client.set_screening_contract(&Some(screener_id));
+    execute_queued(&env, &client);
+    assert!(!client.is_address_allowed(&recipient));
+
+    let result = client.try_create_escrow(
+        &2u64,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones(&env, &[10_000]),
+        &1_706_400_000u64,
+    );
+    assert_eq!(result, Err(Ok(Error::AddressBlocked)));
+    let result = client.try_release_milestone(&1u64, &0);
+    assert_eq!(result, Err(Ok(Error::AddressBlocked)));
+
+    // A screener address without a contract behaves the same
+    client.set_screening_contract(&Some(Address::generate(&env)));
+    execute_queued(&env, &client);
+    assert!(!client.is_address_allowed(&recipient));
+}
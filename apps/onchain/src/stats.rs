@@ -0,0 +1,173 @@
+// stats.rs
+//! Contract-wide aggregates for dashboards.
+//!
+//! Per-token totals are updated whenever escrowed funds leave the contract or
+//! fees accrue, and escrow counts per status on every status transition. Total
+//! value locked is the token's escrow liabilities, less payouts credited to
+//! their payees but not yet withdrawn.
+#[cfg(test)]
+use crate::__VaultixEscrow_fn_set_registry;
+use crate::archive::{is_terminal, record_settlement};
+use crate::{
+    get_liabilities, get_persistent, set_persistent, DataKey, Error, Escrow, EscrowStatus,
+    VaultixEscrow, VaultixEscrowClient,
+};
+use soroban_sdk::{contractimpl, contracttype, Address, Env};
+
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TokenStats {
+    pub total_value_locked: i128,
+    pub total_released: i128, // Paid to recipients
+    pub total_refunded: i128, // Returned to depositors, including unused fee reserves
+    pub fees_collected: i128,
+    pub total_credited: i128, // Released or refunded as credits not yet withdrawn
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StatusCounts {
+    pub created: u64,
+    pub active: u64,
+    pub completed: u64,
+    pub cancelled: u64,
+    pub disputed: u64,
+    pub resolved: u64,
+    pub expired: u64,
+}
+
+/// Running per-token totals; total value locked is derived from liabilities instead.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Default)]
+struct TokenTotals {
+    pub released: i128,
+    pub refunded: i128,
+    pub fees_collected: i128,
+    pub credited: i128,
+}
+
+#[contractimpl]
+impl VaultixEscrow {
+    pub fn get_stats(env: Env, token_address: Address) -> TokenStats {
+        let totals = get_token_totals(&env, &token_address);
+        TokenStats {
+            total_value_locked: get_liabilities(&env, &token_address)
+                .saturating_sub(totals.credited)
+                .max(0),
+            total_released: totals.released,
+            total_refunded: totals.refunded,
+            fees_collected: totals.fees_collected,
+            total_credited: totals.credited,
+        }
+    }
+
+    pub fn get_status_counts(env: Env) -> StatusCounts {
+        get_status_counts(&env)
+    }
+}
+
+fn get_token_totals(env: &Env, token_address: &Address) -> TokenTotals {
+    get_persistent(env, &DataKey::TokenTotals(token_address.clone())).unwrap_or_default()
+}
+
+fn update_token_totals(
+    env: &Env,
+    token_address: &Address,
+    update: impl FnOnce(&mut TokenTotals) -> Option<()>,
+) -> Result<(), Error> {
+    let mut totals = get_token_totals(env, token_address);
+    update(&mut totals).ok_or(Error::InvalidMilestoneAmount)?;
+    set_persistent(env, &DataKey::TokenTotals(token_address.clone()), &totals);
+    Ok(())
+}
+
+fn get_status_counts(env: &Env) -> StatusCounts {
+    env.storage()
+        .instance()
+        .get(&DataKey::StatusCounts)
+        .unwrap_or_default()
+}
+
+fn status_count(counts: &mut StatusCounts, status: EscrowStatus) -> &mut u64 {
+    match status {
+        EscrowStatus::Created => &mut counts.created,
+        EscrowStatus::Active => &mut counts.active,
+        EscrowStatus::Completed => &mut counts.completed,
+        EscrowStatus::Cancelled => &mut counts.cancelled,
+        EscrowStatus::Disputed => &mut counts.disputed,
+        EscrowStatus::Resolved => &mut counts.resolved,
+        EscrowStatus::Expired => &mut counts.expired,
+    }
+}
+
+/// Moves one escrow from status `from` (none for a new escrow) to `to` in the counts.
+pub(crate) fn record_status_change(env: &Env, from: Option<EscrowStatus>, to: EscrowStatus) {
+    let mut counts = get_status_counts(env);
+    if let Some(from) = from {
+        let count = status_count(&mut counts, from);
+        *count = count.saturating_sub(1);
+    }
+    *status_count(&mut counts, to) += 1;
+    env.storage()
+        .instance()
+        .set(&DataKey::StatusCounts, &counts);
+}
+
+/// Sets the status of an escrow and updates the status counts, recording the
+/// settlement time when the new status is terminal.
+pub(crate) fn set_status(env: &Env, escrow_id: u64, escrow: &mut Escrow, status: EscrowStatus) {
+    record_status_change(env, Some(escrow.status), status);
+    if is_terminal(status) {
+        record_settlement(env, escrow_id);
+    }
+    escrow.status = status;
+}
+
+/// Records `amount` paid out of `escrow` to `to` as released to the recipient or
+/// refunded to the depositor.
+pub(crate) fn record_payout(
+    env: &Env,
+    escrow: &Escrow,
+    to: &Address,
+    amount: i128,
+) -> Result<(), Error> {
+    let to_recipient = *to == escrow.recipient;
+    update_token_totals(env, &escrow.token_address, |totals| {
+        if to_recipient {
+            totals.released = totals.released.checked_add(amount)?;
+        } else {
+            totals.refunded = totals.refunded.checked_add(amount)?;
+        }
+        Some(())
+    })
+}
+
+pub(crate) fn record_fee_collected(
+    env: &Env,
+    token_address: &Address,
+    fee: i128,
+) -> Result<(), Error> {
+    update_token_totals(env, token_address, |totals| {
+        totals.fees_collected = totals.fees_collected.checked_add(fee)?;
+        Some(())
+    })
+}
+
+/// Records `amount` credited to a payee, which stays in liabilities until withdrawn.
+pub(crate) fn record_credit(env: &Env, token_address: &Address, amount: i128) -> Result<(), Error> {
+    update_token_totals(env, token_address, |totals| {
+        totals.credited = totals.credited.checked_add(amount)?;
+        Some(())
+    })
+}
+
+pub(crate) fn record_credit_withdrawn(
+    env: &Env,
+    token_address: &Address,
+    amount: i128,
+) -> Result<(), Error> {
+    update_token_totals(env, token_address, |totals| {
+        totals.credited = totals.credited.saturating_sub(amount).max(0);
+        Some(())
+    })
+}
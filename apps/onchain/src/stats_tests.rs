@@ -0,0 +1,86 @@
+use crate::test_fixtures::{create_funded, milestones, setup};
+use crate::{Role, StatusCounts, TokenStats};
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+#[test]
+fn test_stats_follow_escrow_lifecycle() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_address) = setup(&env, None);
+
+    assert_eq!(client.get_stats(&token_address), TokenStats::default());
+    assert_eq!(client.get_status_counts(), StatusCounts::default());
+
+    create_funded(&env, &client, 1, &token_address, &[10_000]);
+    create_funded(&env, &client, 2, &token_address, &[5_000]);
+    client.create_escrow(
+        &3,
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &token_address,
+        &milestones(&env, &[1_000]),
+        &1_706_400_000u64,
+    );
+
+    assert_eq!(client.get_stats(&token_address).total_value_locked, 15_000);
+    assert_eq!(
+        client.get_status_counts(),
+        StatusCounts {
+            created: 1,
+            active: 2,
+            ..StatusCounts::default()
+        }
+    );
+
+    // 50 bps is retained on both the release and the cancellation
+    client.release_milestone(&1, &0);
+    client.complete_escrow(&1);
+    client.cancel_escrow(&2);
+
+    assert_eq!(
+        client.get_stats(&token_address),
+        TokenStats {
+            total_value_locked: 0,
+            total_released: 9_950,
+            total_refunded: 4_975,
+            fees_collected: 75,
+            total_credited: 0,
+        }
+    );
+    assert_eq!(
+        client.get_status_counts(),
+        StatusCounts {
+            created: 1,
+            completed: 1,
+            cancelled: 1,
+            ..StatusCounts::default()
+        }
+    );
+}
+
+#[test]
+fn test_dispute_payouts_are_split_between_released_and_refunded() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token_address) = setup(&env, None);
+    let arbitrator = client.get_role(&Role::Arbitrator);
+
+    let (depositor, recipient) = create_funded(&env, &client, 1, &token_address, &[10_000]);
+    client.raise_dispute(&1, &depositor);
+    assert_eq!(client.get_status_counts().disputed, 1);
+
+    client.resolve_dispute(&arbitrator, &1, &recipient, &Some(6_000));
+
+    let stats = client.get_stats(&token_address);
+    assert_eq!(stats.total_released, 6_000);
+    assert_eq!(stats.total_refunded, 4_000);
+    assert_eq!(stats.fees_collected, 0);
+    assert_eq!(stats.total_value_locked, 0);
+    assert_eq!(
+        client.get_status_counts(),
+        StatusCounts {
+            resolved: 1,
+            ..StatusCounts::default()
+        }
+    );
+}
@@ -0,0 +1,151 @@
+// storage.rs
+//! Typed storage keys and the migration from the untyped key layout.
+//!
+//! Version 1 keyed every entry by a short symbol, optionally in a tuple with its
+//! identifiers (e.g. `("escrow", id)`). All storage now goes through `DataKey`;
+//! `migrate` moves version 1 entries across with the helpers below.
+use crate::{extend_persistent_ttl, ContractState, EscrowV1, PausableOperation, Role};
+use soroban_sdk::{
+    contracttype, symbol_short, Address, Env, IntoVal, Symbol, TryFromVal, Val, Vec,
+};
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DataKey {
+    // Instance storage
+    Version,
+    Migration,
+    Config,
+    PauseState,
+    PausedAt,
+    OperationPaused(PausableOperation),
+    TokenPaused(Address),
+    PendingUpgrade,
+    AllowedTokens,
+    QueuedChangeIds,
+    QueuedChangeCounter,
+    Multisig,
+    MultisigCounter,
+    StatusCounts,
+    RoleAdmin(Role),
+    // Persistent storage
+    Escrow(u64),
+    Milestone(u64, u32),
+    EscrowFee(u64),
+    SettledAt(u64),
+    ArchivedMilestones(u64), // Milestones removed so far of an escrow being archived
+    Receipt(u64),
+    TokenFee(Address),
+    PendingFeeChange,
+    PendingTokenFeeChange(Address),
+    FeeDiscount(Address),
+    AccruedFees(Address),
+    TokenLimits(Address),
+    Liabilities(Address),
+    TokenTotals(Address),
+    Credit(Address, Address),
+    PendingRoleTransfer(Role),
+    RoleMember(Role, Address),
+    QueuedChange(u64),
+    MultisigProposal(u64),
+    Blocked(Address),
+    DepositorIndex(Address, u32),
+    DepositorIndexLen(Address),
+    RecipientIndex(Address, u32),
+    RecipientIndexLen(Address),
+}
+
+/// Storage key for a scheduled fee change.
+/// `None` addresses the global fee, `Some(token)` a token-level override.
+pub(crate) fn pending_fee_key(token_address: &Option<Address>) -> DataKey {
+    match token_address {
+        None => DataKey::PendingFeeChange,
+        Some(token_address) => DataKey::PendingTokenFeeChange(token_address.clone()),
+    }
+}
+
+/// Whether any version 1 configuration is still in place, before `migrate` has run.
+/// Version 1 set roles and treasury settings independently, so a deployment may
+/// hold some of these keys without the others.
+pub(crate) fn has_legacy_config(env: &Env) -> bool {
+    let persistent = env.storage().persistent();
+    let instance = env.storage().instance();
+    persistent.has(&symbol_short!("admin"))
+        || persistent.has(&Symbol::new(env, "operator"))
+        || persistent.has(&Symbol::new(env, "arbitrator"))
+        || instance.has(&symbol_short!("treasury"))
+        || instance.has(&symbol_short!("fee_bps"))
+        || instance.has(&symbol_short!("state"))
+}
+
+/// Whether a version 1 escrow is still stored under `escrow_id`.
+pub(crate) fn has_legacy_escrow(env: &Env, escrow_id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .has(&(symbol_short!("escrow"), escrow_id))
+}
+
+fn move_instance<K: IntoVal<Env, Val>>(env: &Env, legacy: &K, key: DataKey) {
+    let instance = env.storage().instance();
+    if let Some(value) = instance.get::<_, Val>(legacy) {
+        instance.set(&key, &value);
+        instance.remove(legacy);
+    }
+}
+
+fn move_persistent<K: IntoVal<Env, Val>>(env: &Env, legacy: &K, key: DataKey) {
+    let persistent = env.storage().persistent();
+    if let Some(value) = persistent.get::<_, Val>(legacy) {
+        persistent.set(&key, &value);
+        extend_persistent_ttl(env, &key);
+        persistent.remove(legacy);
+    }
+}
+
+fn move_token_fee(env: &Env, token_address: &Address) {
+    move_persistent(
+        env,
+        &(symbol_short!("tokfee"), token_address.clone()),
+        DataKey::TokenFee(token_address.clone()),
+    );
+}
+
+/// Moves the version 1 contract-wide entries and the fee overrides of
+/// `token_addresses` to their `DataKey`. Version 1 did not record when it was
+/// paused, so a pause carried over starts counting at migration time.
+pub(crate) fn migrate_legacy_keys(env: &Env, token_addresses: &Vec<Address>) {
+    move_instance(env, &symbol_short!("state"), DataKey::PauseState);
+    let instance = env.storage().instance();
+    if instance.get(&DataKey::PauseState) == Some(ContractState::Paused)
+        && !instance.has(&DataKey::PausedAt)
+    {
+        instance.set(&DataKey::PausedAt, &env.ledger().timestamp());
+    }
+    for token_address in token_addresses.iter() {
+        move_token_fee(env, &token_address);
+    }
+}
+
+/// Returns the version 1 escrow stored under `escrow_id`, if there is one.
+pub(crate) fn get_legacy_escrow(env: &Env, escrow_id: u64) -> Option<EscrowV1> {
+    let raw: Val = env
+        .storage()
+        .persistent()
+        .get(&(symbol_short!("escrow"), escrow_id))?;
+    EscrowV1::try_from_val(env, &raw).ok()
+}
+
+/// Removes the version 1 escrow stored under `escrow_id`, moving its fee
+/// override and its token's across.
+pub(crate) fn remove_legacy_escrow(env: &Env, escrow_id: u64, token_address: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&(symbol_short!("escrow"), escrow_id));
+
+    move_persistent(
+        env,
+        &(symbol_short!("escfee"), escrow_id),
+        DataKey::EscrowFee(escrow_id),
+    );
+    move_token_fee(env, token_address);
+}
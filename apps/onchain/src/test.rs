@@ -1,28 +1,14 @@
 // test.rs
 use super::*;
+use crate::test_fixtures::{
+    create_funded, create_test_token, create_token_contract, execute_queued, init_contract,
+    init_with_token, milestones as fixture_milestones, register_contract, setup,
+};
 use soroban_sdk::{
     testutils::{Address as _, Events, Ledger},
     token, vec, Address, Env, IntoVal,
 };
 
-/// Helper function to create and initialize a test token
-/// Returns admin client for minting and the token address
-fn create_test_token<'a>(env: &Env, admin: &Address) -> (token::StellarAssetClient<'a>, Address) {
-    let token_address = env.register_stellar_asset_contract(admin.clone());
-    let token_admin_client = token::StellarAssetClient::new(env, &token_address);
-    (token_admin_client, token_address)
-}
-
-/// Helper function to create token client + admin + address
-fn create_token_contract<'a>(
-    env: &Env,
-    admin: &Address,
-) -> (token::Client<'a>, token::StellarAssetClient<'a>, Address) {
-    let (token_admin, token_address) = create_test_token(env, admin);
-    let token_client = token::Client::new(env, &token_address);
-    (token_client, token_admin, token_address)
-}
-
 #[test]
 fn test_create_escrow_fails_when_paused() {
     let env = Env::default();
@@ -32,7 +18,6 @@ fn test_create_escrow_fails_when_paused() {
     let client = VaultixEscrowClient::new(&env, &contract_id);
 
     let treasury = Address::generate(&env);
-    client.initialize(&treasury, &None);
 
     let depositor = Address::generate(&env);
     let recipient = Address::generate(&env);
@@ -42,10 +27,17 @@ fn test_create_escrow_fails_when_paused() {
     let escrow_id = 1_000u64;
 
     // 1. Initialize roles FIRST
-    client.init(&admin, &operator, &arbitrator);
+    client.init(
+        &admin,
+        &operator,
+        &arbitrator,
+        &treasury,
+        &None,
+        &MIN_GOVERNANCE_DELAY,
+    );
 
     // 2. NOW pause the contract (using the operator we just initialized)
-    client.set_paused(&true);
+    client.set_paused(&operator, &true);
 
     let (_token_client, token_admin, token_address) = create_token_contract(&env, &admin);
     token_admin.mint(&depositor, &10_000);
@@ -60,6 +52,8 @@ fn test_create_escrow_fails_when_paused() {
 
     let deadline = 1_706_400_000u64;
 
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
     let result = client.try_create_escrow(
         &escrow_id,
         &depositor,
@@ -81,14 +75,20 @@ fn test_deposit_funds_fails_when_paused() {
     let client = VaultixEscrowClient::new(&env, &contract_id);
 
     let treasury = Address::generate(&env);
-    client.initialize(&treasury, &None);
 
     let depositor = Address::generate(&env);
     let recipient = Address::generate(&env);
     let admin = Address::generate(&env);
     let operator = Address::generate(&env);
     let arbitrator = Address::generate(&env);
-    client.init(&admin, &operator, &arbitrator);
+    client.init(
+        &admin,
+        &operator,
+        &arbitrator,
+        &treasury,
+        &None,
+        &MIN_GOVERNANCE_DELAY,
+    );
     let escrow_id = 1_001u64;
 
     let (token_client, token_admin, token_address) = create_token_contract(&env, &admin);
@@ -104,6 +104,8 @@ fn test_deposit_funds_fails_when_paused() {
     ];
 
     let deadline = 1_706_400_000u64;
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -115,11 +117,153 @@ fn test_deposit_funds_fails_when_paused() {
 
     token_client.approve(&depositor, &contract_id, &10_000, &200);
 
-    client.set_paused(&true);
+    client.set_paused(&operator, &true);
     let result = client.try_deposit_funds(&escrow_id);
     assert_eq!(result, Err(Ok(Error::ContractPaused)));
 }
 
+#[test]
+fn test_operation_and_token_pause_are_scoped() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token_address) = setup(&env, None);
+    let operator = client.get_role(&Role::Operator);
+    let (depositor, recipient) = create_funded(&env, &client, 1, &token_address, &[10_000]);
+
+    // Pausing releases still lets the depositor open a dispute
+    client.set_operation_paused(&operator, &PausableOperation::Release, &true);
+    assert!(client.is_paused(&PausableOperation::Release, &None));
+    assert!(!client.is_paused(&PausableOperation::Dispute, &None));
+    let result = client.try_release_milestone(&1u64, &0);
+    assert_eq!(result, Err(Ok(Error::ContractPaused)));
+    client.set_operation_paused(&operator, &PausableOperation::Release, &false);
+    execute_queued(&env, &client);
+
+    // Pausing the token blocks new escrows in that token only
+    client.set_token_paused(&operator, &token_address, &true);
+    assert!(client.is_paused(&PausableOperation::Create, &Some(token_address.clone())));
+    let milestones = fixture_milestones(&env, &[10_000]);
+    let result = client.try_create_escrow(
+        &2u64,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1_706_400_000u64,
+    );
+    assert_eq!(result, Err(Ok(Error::ContractPaused)));
+
+    let (_, other_token) = create_test_token(&env, &Address::generate(&env));
+    client.allow_token(&operator, &other_token, &1, &i128::MAX);
+    execute_queued(&env, &client);
+    client.create_escrow(
+        &3u64,
+        &depositor,
+        &recipient,
+        &other_token,
+        &milestones,
+        &1_706_400_000u64,
+    );
+}
+
+#[test]
+fn test_emergency_withdraw_after_prolonged_pause() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token_address) = setup(&env, None);
+    let operator = client.get_role(&Role::Operator);
+    client.set_emergency_exit_delay(&86_400);
+    execute_queued(&env, &client);
+
+    let (depositor, _) = create_funded(&env, &client, 1, &token_address, &[4_000, 6_000]);
+    client.release_milestone(&1u64, &0);
+
+    let result = client.try_emergency_withdraw(&1u64);
+    assert_eq!(result, Err(Ok(Error::EmergencyExitUnavailable)));
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.set_paused(&operator, &true);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000 + 86_399);
+    let result = client.try_emergency_withdraw(&1u64);
+    assert_eq!(result, Err(Ok(Error::EmergencyExitUnavailable)));
+
+    // Unreleased funds return in full, with no fee charged
+    env.ledger().with_mut(|li| li.timestamp = 1_000 + 86_400);
+    client.emergency_withdraw(&1u64);
+    assert_eq!(
+        token::Client::new(&env, &token_address).balance(&depositor),
+        6_000
+    );
+    assert_eq!(client.get_state(&1u64), EscrowStatus::Cancelled);
+}
+
+#[test]
+fn test_emergency_withdraw_waits_until_every_exit_is_paused() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token_address) = setup(&env, None);
+    let operator = client.get_role(&Role::Operator);
+    let (depositor, _) = create_funded(&env, &client, 1, &token_address, &[4_000, 6_000]);
+    let delay = client.get_emergency_exit_delay();
+
+    // With releases and refunds paused, a dispute is still a way out
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.set_operation_paused(&operator, &PausableOperation::Release, &true);
+    client.set_operation_paused(&operator, &PausableOperation::Refund, &true);
+    env.ledger().with_mut(|li| li.timestamp = 1_000 + delay);
+    let result = client.try_emergency_withdraw(&1u64);
+    assert_eq!(result, Err(Ok(Error::EmergencyExitUnavailable)));
+
+    // The delay counts from when the last way out was paused
+    client.set_operation_paused(&operator, &PausableOperation::Dispute, &true);
+    env.ledger()
+        .with_mut(|li| li.timestamp = 1_000 + 2 * delay - 1);
+    let result = client.try_emergency_withdraw(&1u64);
+    assert_eq!(result, Err(Ok(Error::EmergencyExitUnavailable)));
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000 + 2 * delay);
+    client.emergency_withdraw(&1u64);
+    assert_eq!(
+        token::Client::new(&env, &token_address).balance(&depositor),
+        10_000
+    );
+    assert_eq!(client.get_state(&1u64), EscrowStatus::Cancelled);
+}
+
+#[test]
+fn test_emergency_withdraw_leaves_disputes_to_the_arbitrator() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token_address) = setup(&env, None);
+    let operator = client.get_role(&Role::Operator);
+    let (depositor, _) = create_funded(&env, &client, 1, &token_address, &[4_000, 6_000]);
+    client.raise_dispute(&1u64, &depositor);
+    let delay = client.get_emergency_exit_delay();
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.set_paused(&operator, &true);
+    env.ledger().with_mut(|li| li.timestamp = 1_000 + delay);
+    let result = client.try_emergency_withdraw(&1u64);
+    assert_eq!(result, Err(Ok(Error::InvalidEscrowStatus)));
+    assert_eq!(client.get_state(&1u64), EscrowStatus::Disputed);
+}
+
+#[test]
+fn test_emergency_exit_delay_is_capped() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup(&env, None);
+    client.set_emergency_exit_delay(&u64::MAX);
+    execute_queued(&env, &client);
+    assert_eq!(client.get_emergency_exit_delay(), MAX_EMERGENCY_EXIT_DELAY);
+}
+
 #[test]
 fn test_create_and_get_escrow() {
     let env = Env::default();
@@ -158,6 +302,7 @@ fn test_create_and_get_escrow() {
 
     let deadline = 1706400000u64;
 
+    init_with_token(&env, &client, &token_address);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -174,7 +319,8 @@ fn test_create_and_get_escrow() {
     assert_eq!(escrow.total_amount, 10000);
     assert_eq!(escrow.total_released, 0);
     assert_eq!(escrow.status, EscrowStatus::Created);
-    assert_eq!(escrow.milestones.len(), 3);
+    assert_eq!(escrow.milestone_count, 3);
+    assert_eq!(escrow.released_count, 0);
 
     // Verify Create Event (Refactored Schema)
     let events = env.events().all();
@@ -185,22 +331,24 @@ fn test_create_and_get_escrow() {
     let expected_topics: soroban_sdk::Vec<soroban_sdk::Val> = (
         Symbol::new(&env, "Vaultix"),
         Symbol::new(&env, "EscrowCreated"),
+        EVENT_SCHEMA_VERSION,
         escrow_id,
     )
         .into_val(&env);
     assert_eq!(event.1, expected_topics);
 
-    // Payload assertion: Convert event.2 into a Vec<Val> and compare with expected Vec<Val>
-    let actual_payload: soroban_sdk::Vec<soroban_sdk::Val> = event.2.into_val(&env);
-    let expected_payload: soroban_sdk::Vec<soroban_sdk::Val> = vec![
-        &env,
-        depositor.clone().into_val(&env),
-        recipient.clone().into_val(&env),
-        token_address.clone().into_val(&env),
-        10000i128.into_val(&env),
-        deadline.into_val(&env),
-    ];
-    assert_eq!(actual_payload, expected_payload);
+    // Payload assertion: the data decodes as the event struct
+    let payload: EscrowCreated = event.2.into_val(&env);
+    assert_eq!(
+        payload,
+        EscrowCreated {
+            depositor: depositor.clone(),
+            recipient: recipient.clone(),
+            token_address: token_address.clone(),
+            total_amount: 10000,
+            deadline,
+        }
+    );
 
     assert_eq!(escrow.deadline, deadline);
 
@@ -243,6 +391,7 @@ fn test_deposit_funds() {
     ];
 
     // Create escrow
+    init_with_token(&env, &client, &token_address);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -283,7 +432,8 @@ fn test_release_milestone_with_tokens() {
 
     // Initialize treasury (fee-free for test)
     let treasury = Address::generate(&env);
-    client.initialize(&treasury, &Some(0));
+    init_contract(&env, &client, &treasury, &Some(0));
+    let operator = client.get_role(&Role::Operator);
 
     // Setup token
     let (token_client, token_admin, token_address) = create_token_contract(&env, &admin);
@@ -305,6 +455,8 @@ fn test_release_milestone_with_tokens() {
     ];
 
     // Create and fund escrow
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -330,12 +482,13 @@ fn test_release_milestone_with_tokens() {
     // Verify escrow state
     let escrow = client.get_escrow(&escrow_id);
     assert_eq!(escrow.total_released, 6000);
+    assert_eq!(escrow.released_count, 1);
     assert_eq!(
-        escrow.milestones.get(0).unwrap().status,
+        client.get_milestone(&escrow_id, &0).status,
         MilestoneStatus::Released
     );
     assert_eq!(
-        escrow.milestones.get(1).unwrap().status,
+        client.get_milestone(&escrow_id, &1).status,
         MilestoneStatus::Pending
     );
 
@@ -369,6 +522,7 @@ fn test_dispute_blocks_release() {
         },
     ];
 
+    init_with_token(&env, &client, &token_address);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -421,6 +575,7 @@ fn test_complete_escrow_with_all_releases() {
     ];
 
     // Create and fund escrow
+    init_with_token(&env, &client, &token_address);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -474,6 +629,7 @@ fn test_cancel_escrow_with_refund() {
     ];
 
     // Create and fund escrow
+    init_with_token(&env, &client, &token_address);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -498,6 +654,27 @@ fn test_cancel_escrow_with_refund() {
 
     let escrow = client.get_escrow(&escrow_id);
     assert_eq!(escrow.status, EscrowStatus::Cancelled);
+
+    // Debug tracing is only published by `debug-events` builds
+    let events = env.events().all();
+    let event = events.last().unwrap();
+    let payload: EscrowCancelled = event.2.into_val(&env);
+    assert_eq!(
+        payload,
+        EscrowCancelled {
+            depositor: depositor.clone(),
+            refund: 10_000,
+            fee: 0,
+        }
+    );
+    let cancel_start = Symbol::new(&env, "CancelStart");
+    let traced = events.iter().any(|(_, topics, _)| {
+        topics
+            .get(1)
+            .and_then(|t| Symbol::try_from_val(&env, &t).ok())
+            .is_some_and(|name| name == cancel_start)
+    });
+    assert_eq!(traced, cfg!(feature = "debug-events"));
 }
 
 #[test]
@@ -525,6 +702,7 @@ fn test_cancel_unfunded_escrow() {
     ];
 
     // Create escrow but don't fund it
+    init_with_token(&env, &client, &token_address);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -559,7 +737,15 @@ fn test_admin_resolves_dispute_to_recipient() {
     let (token_client, token_admin, token_address) = create_token_contract(&env, &admin);
     token_admin.mint(&depositor, &10000);
 
-    client.init(&admin, &operator, &arbitrator);
+    let treasury = Address::generate(&env);
+    client.init(
+        &admin,
+        &operator,
+        &arbitrator,
+        &treasury,
+        &None,
+        &MIN_GOVERNANCE_DELAY,
+    );
 
     let milestones = vec![
         &env,
@@ -575,6 +761,8 @@ fn test_admin_resolves_dispute_to_recipient() {
         },
     ];
 
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -589,14 +777,14 @@ fn test_admin_resolves_dispute_to_recipient() {
 
     client.raise_dispute(&escrow_id, &recipient);
 
-    client.resolve_dispute(&escrow_id, &recipient, &None);
+    client.resolve_dispute(&arbitrator, &escrow_id, &recipient, &None);
 
     let escrow = client.get_escrow(&escrow_id);
     assert_eq!(escrow.status, EscrowStatus::Resolved);
     assert_eq!(escrow.resolution, Resolution::Recipient);
     assert_eq!(escrow.total_released, escrow.total_amount);
-    assert!(escrow
-        .milestones
+    assert!(client
+        .get_milestones(&escrow_id, &0, &escrow.milestone_count)
         .iter()
         .all(|m| m.status == MilestoneStatus::Released));
 
@@ -623,7 +811,15 @@ fn test_admin_resolves_dispute_to_depositor() {
     let (token_client, token_admin, token_address) = create_token_contract(&env, &admin);
     token_admin.mint(&depositor, &5000);
 
-    client.init(&admin, &operator, &arbitrator);
+    let treasury = Address::generate(&env);
+    client.init(
+        &admin,
+        &operator,
+        &arbitrator,
+        &treasury,
+        &None,
+        &MIN_GOVERNANCE_DELAY,
+    );
 
     let milestones = vec![
         &env,
@@ -639,6 +835,8 @@ fn test_admin_resolves_dispute_to_depositor() {
         },
     ];
 
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -653,14 +851,14 @@ fn test_admin_resolves_dispute_to_depositor() {
 
     client.raise_dispute(&escrow_id, &depositor);
 
-    client.resolve_dispute(&escrow_id, &depositor, &None);
+    client.resolve_dispute(&arbitrator, &escrow_id, &depositor, &None);
 
     let escrow = client.get_escrow(&escrow_id);
     assert_eq!(escrow.status, EscrowStatus::Resolved);
     assert_eq!(escrow.resolution, Resolution::Depositor);
     assert_eq!(escrow.total_released, 0);
-    assert!(escrow
-        .milestones
+    assert!(client
+        .get_milestones(&escrow_id, &0, &escrow.milestone_count)
         .iter()
         .all(|m| m.status == MilestoneStatus::Disputed));
 
@@ -682,7 +880,7 @@ fn test_raise_dispute_happy_path() {
     let admin = Address::generate(&env);
     let escrow_id = 20u64;
 
-    let (_token_client, token_admin, token_address) = create_token_contract(&env, &admin);
+    let (token_client, token_admin, token_address) = create_token_contract(&env, &admin);
     token_admin.mint(&depositor, &1000);
 
     let milestones = vec![
@@ -699,6 +897,7 @@ fn test_raise_dispute_happy_path() {
         },
     ];
 
+    init_with_token(&env, &client, &token_address);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -707,6 +906,8 @@ fn test_raise_dispute_happy_path() {
         &milestones,
         &1706400000u64,
     );
+    token_client.approve(&depositor, &contract_id, &1000, &200);
+    client.deposit_funds(&escrow_id);
 
     let events_before = env.events().all().len();
 
@@ -715,8 +916,8 @@ fn test_raise_dispute_happy_path() {
     let escrow = client.get_escrow(&escrow_id);
     assert_eq!(escrow.status, EscrowStatus::Disputed);
     assert_eq!(escrow.resolution, Resolution::None);
-    assert!(escrow
-        .milestones
+    assert!(client
+        .get_milestones(&escrow_id, &0, &escrow.milestone_count)
         .iter()
         .all(|m| m.status == MilestoneStatus::Disputed || m.status == MilestoneStatus::Released));
 
@@ -727,10 +928,13 @@ fn test_raise_dispute_happy_path() {
     let expected_topics: soroban_sdk::Vec<soroban_sdk::Val> = (
         Symbol::new(&env, "Vaultix"),
         Symbol::new(&env, "DisputeRaised"),
+        EVENT_SCHEMA_VERSION,
         escrow_id,
     )
         .into_val(&env);
     assert_eq!(event.1, expected_topics);
+    let payload: DisputeRaised = event.2.into_val(&env);
+    assert_eq!(payload.raised_by, depositor);
 }
 
 #[test]
@@ -746,6 +950,7 @@ fn test_raise_dispute_invalid_status() {
     let admin = Address::generate(&env);
     let escrow_id_completed = 21u64;
     let escrow_id_cancelled = 22u64;
+    let escrow_id_unfunded = 23u64;
 
     let (token_client, token_admin, token_address) = create_token_contract(&env, &admin);
     token_admin.mint(&depositor, &10_000);
@@ -760,6 +965,7 @@ fn test_raise_dispute_invalid_status() {
     ];
 
     // Completed escrow
+    init_with_token(&env, &client, &token_address);
     client.create_escrow(
         &escrow_id_completed,
         &depositor,
@@ -792,6 +998,19 @@ fn test_raise_dispute_invalid_status() {
 
     let result_cancelled = client.try_raise_dispute(&escrow_id_cancelled, &depositor);
     assert_eq!(result_cancelled, Err(Ok(Error::InvalidEscrowStatus)));
+
+    // Unfunded escrow holds no principal to dispute
+    client.create_escrow(
+        &escrow_id_unfunded,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+
+    let result_unfunded = client.try_raise_dispute(&escrow_id_unfunded, &recipient);
+    assert_eq!(result_unfunded, Err(Ok(Error::InvalidEscrowStatus)));
 }
 
 #[test]
@@ -813,7 +1032,15 @@ fn test_resolve_dispute_invalid_winner_or_overflow() {
     let (token_client, token_admin, token_address) = create_token_contract(&env, &admin);
     token_admin.mint(&depositor, &1000);
 
-    client.init(&admin, &operator, &arbitrator);
+    let treasury = Address::generate(&env);
+    client.init(
+        &admin,
+        &operator,
+        &arbitrator,
+        &treasury,
+        &None,
+        &MIN_GOVERNANCE_DELAY,
+    );
 
     let milestones = vec![
         &env,
@@ -824,6 +1051,8 @@ fn test_resolve_dispute_invalid_winner_or_overflow() {
         },
     ];
 
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -838,7 +1067,8 @@ fn test_resolve_dispute_invalid_winner_or_overflow() {
     client.raise_dispute(&escrow_id, &depositor);
 
     // Invalid winner
-    let result_invalid_winner = client.try_resolve_dispute(&escrow_id, &outsider, &None);
+    let result_invalid_winner =
+        client.try_resolve_dispute(&arbitrator, &escrow_id, &outsider, &None);
     assert_eq!(result_invalid_winner, Err(Ok(Error::InvalidWinner)));
 }
 
@@ -851,7 +1081,6 @@ fn test_resolve_dispute_while_paused() {
     let client = VaultixEscrowClient::new(&env, &contract_id);
 
     let treasury = Address::generate(&env);
-    client.initialize(&treasury, &None);
 
     let admin = Address::generate(&env);
     let operator = Address::generate(&env);
@@ -863,7 +1092,14 @@ fn test_resolve_dispute_while_paused() {
     let (token_client, token_admin, token_address) = create_token_contract(&env, &admin);
     token_admin.mint(&depositor, &5000);
 
-    client.init(&admin, &operator, &arbitrator);
+    client.init(
+        &admin,
+        &operator,
+        &arbitrator,
+        &treasury,
+        &None,
+        &MIN_GOVERNANCE_DELAY,
+    );
 
     let milestones = vec![
         &env,
@@ -874,6 +1110,8 @@ fn test_resolve_dispute_while_paused() {
         },
     ];
 
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -888,10 +1126,15 @@ fn test_resolve_dispute_while_paused() {
     client.raise_dispute(&escrow_id, &depositor);
 
     // Pause contract after dispute is raised
-    client.set_paused(&true);
+    client.set_paused(&operator, &true);
+
+    // Resolution is blocked while paused and resumes once the pause is lifted
+    let result = client.try_resolve_dispute(&arbitrator, &escrow_id, &depositor, &None);
+    assert_eq!(result, Err(Ok(Error::ContractPaused)));
 
-    // Resolution should still be allowed by admin while paused
-    client.resolve_dispute(&escrow_id, &depositor, &None);
+    client.set_paused(&operator, &false);
+    execute_queued(&env, &client);
+    client.resolve_dispute(&arbitrator, &escrow_id, &depositor, &None);
 
     let escrow = client.get_escrow(&escrow_id);
     assert_eq!(escrow.status, EscrowStatus::Resolved);
@@ -924,6 +1167,7 @@ fn test_duplicate_escrow_id() {
         },
     ];
 
+    init_with_token(&env, &client, &token_address);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -952,7 +1196,8 @@ fn test_double_release() {
 
     // Initialize treasury
     let treasury = Address::generate(&env);
-    client.initialize(&treasury, &Some(50));
+    init_contract(&env, &client, &treasury, &Some(50));
+    let operator = client.get_role(&Role::Operator);
 
     let depositor = Address::generate(&env);
     let recipient = Address::generate(&env);
@@ -971,6 +1216,8 @@ fn test_double_release() {
         },
     ];
 
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -990,6 +1237,73 @@ fn test_double_release() {
     assert_eq!(result, Err(Ok(Error::MilestoneAlreadyReleased)));
 }
 
+#[test]
+fn test_escrow_with_many_milestones() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, VaultixEscrow);
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let escrow_id = 8u64;
+
+    let treasury = Address::generate(&env);
+    init_contract(&env, &client, &treasury, &Some(0));
+    let operator = client.get_role(&Role::Operator);
+
+    let (token_client, token_admin, token_address) = create_token_contract(&env, &admin);
+    token_admin.mint(&depositor, &6_000);
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
+
+    let mut milestones = Vec::new(&env);
+    for _i in 0..15 {
+        milestones.push_back(Milestone {
+            amount: 100,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Task"),
+        });
+    }
+
+    // Milestones beyond what one call can write are appended in chunks
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    for _chunk in 0..3 {
+        client.add_milestones(&escrow_id, &milestones);
+    }
+    assert_eq!(client.get_escrow(&escrow_id).total_amount, 6_000);
+    token_client.approve(&depositor, &contract_id, &6_000, &200);
+    client.deposit_funds(&escrow_id);
+
+    client.release_milestone(&escrow_id, &59);
+    let escrow = client.get_escrow(&escrow_id);
+    assert_eq!(escrow.milestone_count, 60);
+    assert_eq!(escrow.released_count, 1);
+    assert_eq!(
+        client.get_milestone(&escrow_id, &59).status,
+        MilestoneStatus::Released
+    );
+
+    // Pages stop at the last milestone
+    assert_eq!(client.get_milestones(&escrow_id, &0, &10).len(), 10);
+    let tail = client.get_milestones(&escrow_id, &55, &10);
+    assert_eq!(tail.len(), 5);
+    assert_eq!(tail.get(4).unwrap().status, MilestoneStatus::Released);
+
+    let result = client.try_get_milestone(&escrow_id, &60);
+    assert_eq!(result, Err(Ok(Error::MilestoneNotFound)));
+    assert_eq!(token_client.balance(&recipient), 100);
+}
+
 #[test]
 #[should_panic(expected = "Error(Contract, #10)")]
 fn test_too_many_milestones() {
@@ -1016,6 +1330,7 @@ fn test_too_many_milestones() {
         });
     }
 
+    init_with_token(&env, &client, &token_address);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -1052,6 +1367,7 @@ fn test_invalid_milestone_amount() {
         },
     ];
 
+    init_with_token(&env, &client, &token_address);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -1089,6 +1405,7 @@ fn test_unauthorized_confirm_delivery() {
         },
     ];
 
+    init_with_token(&env, &client, &token_address);
     client.create_escrow(
         &escrow_id,
         &buyer,
@@ -1130,6 +1447,7 @@ fn test_double_confirm_delivery() {
         },
     ];
 
+    init_with_token(&env, &client, &token_address);
     client.create_escrow(
         &escrow_id,
         &buyer,
@@ -1171,6 +1489,7 @@ fn test_zero_amount_milestone_rejected() {
         },
     ];
 
+    init_with_token(&env, &client, &token_address);
     let result = client.try_create_escrow(
         &escrow_id,
         &depositor,
@@ -1207,6 +1526,7 @@ fn test_negative_amount_milestone_rejected() {
         },
     ];
 
+    init_with_token(&env, &client, &token_address);
     let result = client.try_create_escrow(
         &escrow_id,
         &depositor,
@@ -1242,6 +1562,7 @@ fn test_self_dealing_rejected() {
         },
     ];
 
+    init_with_token(&env, &client, &token_address);
     let result = client.try_create_escrow(
         &escrow_id,
         &same_party,
@@ -1283,6 +1604,7 @@ fn test_valid_escrow_creation_succeeds() {
         },
     ];
 
+    init_with_token(&env, &client, &token_address);
     let result = client.try_create_escrow(
         &escrow_id,
         &depositor,
@@ -1328,6 +1650,7 @@ fn test_double_deposit_rejected() {
         },
     ];
 
+    init_with_token(&env, &client, &token_address);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -1353,9 +1676,10 @@ fn test_cancel_active_escrow_retains_fee() {
     let client = VaultixEscrowClient::new(&env, &contract_id);
 
     let treasury = Address::generate(&env);
-    client.initialize(&treasury, &Some(50)); // 50 bps = 0.5%
+    init_contract(&env, &client, &treasury, &Some(50)); // 50 bps = 0.5%
 
     let depositor = Address::generate(&env);
+    let operator = client.get_role(&Role::Operator);
     let recipient = Address::generate(&env);
     let admin = Address::generate(&env);
     let escrow_id = 20u64;
@@ -1372,6 +1696,8 @@ fn test_cancel_active_escrow_retains_fee() {
         },
     ];
 
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -1392,9 +1718,10 @@ fn test_cancel_active_escrow_retains_fee() {
     let expected_fee = 50i128;
     let expected_refund = 10_000i128 - expected_fee;
 
-    assert_eq!(token_client.balance(&treasury), expected_fee);
+    assert_eq!(client.get_accrued_fees(&token_address), expected_fee);
     assert_eq!(token_client.balance(&depositor), expected_refund);
-    assert_eq!(token_client.balance(&contract_id), 0);
+    assert_eq!(token_client.balance(&contract_id), expected_fee);
+    assert_eq!(token_client.balance(&treasury), 0);
 
     let escrow = client.get_escrow(&escrow_id);
     assert_eq!(escrow.status, EscrowStatus::Cancelled);
@@ -1425,6 +1752,7 @@ fn test_release_milestone_before_deposit() {
         },
     ];
 
+    init_with_token(&env, &client, &token_address);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -1455,7 +1783,8 @@ fn test_refund_expired_authorization_check() {
     let escrow_id = 100u64;
 
     // Initialize treasury
-    client.initialize(&treasury, &None);
+    init_contract(&env, &client, &treasury, &None);
+    let operator = client.get_role(&Role::Operator);
 
     let (token_client, token_admin, token_address) = create_token_contract(&env, &admin);
     token_admin.mint(&depositor, &10_000);
@@ -1471,6 +1800,8 @@ fn test_refund_expired_authorization_check() {
 
     // Create and fund escrow with deadline in the past
     let deadline = 1000u64;
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -1503,7 +1834,8 @@ fn test_pause_fails_without_operator_initialized() {
     let client = VaultixEscrowClient::new(&env, &contract_id);
 
     // set_paused requires operator. Operator not set -> OperatorNotInitialized (28)
-    client.set_paused(&true);
+    let operator = Address::generate(&env);
+    client.set_paused(&operator, &true);
 }
 
 #[test]
@@ -1514,39 +1846,10 @@ fn test_resolve_dispute_fails_without_arbitrator_initialized() {
     let contract_id = env.register_contract(None, VaultixEscrow);
     let client = VaultixEscrowClient::new(&env, &contract_id);
 
-    let admin = Address::generate(&env);
-    let depositor = Address::generate(&env);
-    let recipient = Address::generate(&env);
-    let escrow_id = 1u64;
-
-    let (token_client, token_admin, token_address) = create_token_contract(&env, &admin);
-    token_admin.mint(&depositor, &1000);
-
-    let milestones = vec![
-        &env,
-        Milestone {
-            amount: 1000,
-            status: MilestoneStatus::Pending,
-            description: symbol_short!("Task"),
-        },
-    ];
-
-    client.create_escrow(
-        &escrow_id,
-        &depositor,
-        &recipient,
-        &token_address,
-        &milestones,
-        &1706400000u64,
-    );
-    token_client.approve(&depositor, &contract_id, &1000, &200);
-    client.deposit_funds(&escrow_id);
-    client.raise_dispute(&escrow_id, &depositor);
-
+    // The arbitrator role is checked before the escrow is loaded
+    let arbitrator = Address::generate(&env);
     let winner = Address::generate(&env);
-
-    // This should now correctly panic with ArbitratorNotInitialized (29)
-    client.resolve_dispute(&escrow_id, &winner, &None);
+    client.resolve_dispute(&arbitrator, &1u64, &winner, &None);
 }
 // ===============================================================================
 // Configurable Fee Model Tests (Feature #93)
@@ -1582,7 +1885,7 @@ fn test_set_token_fee_valid() {
 
     let treasury = Address::generate(&env);
     let admin = Address::generate(&env);
-    client.initialize(&treasury, &Some(50)); // 0.5% default
+    init_contract(&env, &client, &treasury, &Some(50)); // 0.5% default
 
     let (_token_client, _token_admin, token_address) = create_token_contract(&env, &admin);
 
@@ -1606,7 +1909,7 @@ fn test_set_token_fee_invalid_fee_too_high() {
 
     let treasury = Address::generate(&env);
     let admin = Address::generate(&env);
-    client.initialize(&treasury, &Some(50));
+    init_contract(&env, &client, &treasury, &Some(50));
 
     let (_token_client, _token_admin, token_address) = create_token_contract(&env, &admin);
 
@@ -1642,7 +1945,7 @@ fn test_set_escrow_fee_valid() {
     let client = VaultixEscrowClient::new(&env, &contract_id);
 
     let treasury = Address::generate(&env);
-    client.initialize(&treasury, &Some(50)); // 0.5% default
+    init_contract(&env, &client, &treasury, &Some(50)); // 0.5% default
 
     let escrow_id = 1u64;
 
@@ -1666,7 +1969,7 @@ fn test_set_escrow_fee_invalid_fee_too_high() {
     let client = VaultixEscrowClient::new(&env, &contract_id);
 
     let treasury = Address::generate(&env);
-    client.initialize(&treasury, &Some(50));
+    init_contract(&env, &client, &treasury, &Some(50));
 
     let escrow_id = 1u64;
 
@@ -1684,9 +1987,10 @@ fn test_release_milestone_uses_global_fee_by_default() {
     let client = VaultixEscrowClient::new(&env, &contract_id);
 
     let treasury = Address::generate(&env);
-    client.initialize(&treasury, &Some(100)); // 1% fee
+    init_contract(&env, &client, &treasury, &Some(100)); // 1% fee
 
     let depositor = Address::generate(&env);
+    let operator = client.get_role(&Role::Operator);
     let recipient = Address::generate(&env);
     let admin = Address::generate(&env);
 
@@ -1703,6 +2007,8 @@ fn test_release_milestone_uses_global_fee_by_default() {
         },
     ];
 
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -1724,7 +2030,7 @@ fn test_release_milestone_uses_global_fee_by_default() {
     let expected_payout = 10_000i128 - expected_fee;
 
     assert_eq!(token_client.balance(&recipient), expected_payout);
-    assert_eq!(token_client.balance(&treasury), expected_fee);
+    assert_eq!(client.get_accrued_fees(&token_address), expected_fee);
 }
 
 #[test]
@@ -1736,9 +2042,10 @@ fn test_release_milestone_uses_token_fee_override() {
     let client = VaultixEscrowClient::new(&env, &contract_id);
 
     let treasury = Address::generate(&env);
-    client.initialize(&treasury, &Some(50)); // 0.5% global fee
+    init_contract(&env, &client, &treasury, &Some(50)); // 0.5% global fee
 
     let depositor = Address::generate(&env);
+    let operator = client.get_role(&Role::Operator);
     let recipient = Address::generate(&env);
     let admin = Address::generate(&env);
 
@@ -1747,6 +2054,7 @@ fn test_release_milestone_uses_token_fee_override() {
 
     // Set token-specific fee to 200 bps (2%)
     client.set_token_fee(&token_address, &200);
+    execute_queued(&env, &client);
 
     let escrow_id = 1u64;
     let milestones = vec![
@@ -1758,6 +2066,8 @@ fn test_release_milestone_uses_token_fee_override() {
         },
     ];
 
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -1780,7 +2090,7 @@ fn test_release_milestone_uses_token_fee_override() {
     let expected_payout = 10_000i128 - expected_fee;
 
     assert_eq!(token_client.balance(&recipient), expected_payout);
-    assert_eq!(token_client.balance(&treasury), expected_fee);
+    assert_eq!(client.get_accrued_fees(&token_address), expected_fee);
 }
 
 #[test]
@@ -1792,9 +2102,10 @@ fn test_release_milestone_uses_escrow_fee_override() {
     let client = VaultixEscrowClient::new(&env, &contract_id);
 
     let treasury = Address::generate(&env);
-    client.initialize(&treasury, &Some(50)); // 0.5% global fee
+    init_contract(&env, &client, &treasury, &Some(50)); // 0.5% global fee
 
     let depositor = Address::generate(&env);
+    let operator = client.get_role(&Role::Operator);
     let recipient = Address::generate(&env);
     let admin = Address::generate(&env);
 
@@ -1803,11 +2114,13 @@ fn test_release_milestone_uses_escrow_fee_override() {
 
     // Set token-specific fee to 100 bps (1%)
     client.set_token_fee(&token_address, &100);
+    execute_queued(&env, &client);
 
     let escrow_id = 1u64;
 
     // Set escrow-specific fee to 300 bps (3%) - highest priority
     client.set_escrow_fee(&escrow_id, &300);
+    execute_queued(&env, &client);
 
     let milestones = vec![
         &env,
@@ -1818,6 +2131,8 @@ fn test_release_milestone_uses_escrow_fee_override() {
         },
     ];
 
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -1840,7 +2155,7 @@ fn test_release_milestone_uses_escrow_fee_override() {
     let expected_payout = 10_000i128 - expected_fee;
 
     assert_eq!(token_client.balance(&recipient), expected_payout);
-    assert_eq!(token_client.balance(&treasury), expected_fee);
+    assert_eq!(client.get_accrued_fees(&token_address), expected_fee);
 }
 
 #[test]
@@ -1852,9 +2167,10 @@ fn test_cancel_escrow_uses_token_fee_override() {
     let client = VaultixEscrowClient::new(&env, &contract_id);
 
     let treasury = Address::generate(&env);
-    client.initialize(&treasury, &Some(50)); // 0.5% global fee
+    init_contract(&env, &client, &treasury, &Some(50)); // 0.5% global fee
 
     let depositor = Address::generate(&env);
+    let operator = client.get_role(&Role::Operator);
     let recipient = Address::generate(&env);
     let admin = Address::generate(&env);
 
@@ -1863,6 +2179,7 @@ fn test_cancel_escrow_uses_token_fee_override() {
 
     // Set token-specific fee to 200 bps (2%)
     client.set_token_fee(&token_address, &200);
+    execute_queued(&env, &client);
 
     let escrow_id = 1u64;
     let milestones = vec![
@@ -1874,6 +2191,8 @@ fn test_cancel_escrow_uses_token_fee_override() {
         },
     ];
 
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -1896,7 +2215,7 @@ fn test_cancel_escrow_uses_token_fee_override() {
     let expected_refund = 10_000i128 - expected_fee;
 
     assert_eq!(token_client.balance(&depositor), expected_refund);
-    assert_eq!(token_client.balance(&treasury), expected_fee);
+    assert_eq!(client.get_accrued_fees(&token_address), expected_fee);
 }
 
 // #[test]
@@ -1970,7 +2289,8 @@ fn test_refund_expired_uses_escrow_fee_override() {
     let treasury = Address::generate(&env);
     // client.initialize(&treasury, &Some(50));
     // Note: Ensure your initialize function matches this signature in the contract
-    client.initialize(&treasury, &Some(50));
+    init_contract(&env, &client, &treasury, &Some(50));
+    let operator = client.get_role(&Role::Operator);
 
     let depositor = Address::generate(&env);
     let admin = Address::generate(&env);
@@ -1984,6 +2304,7 @@ fn test_refund_expired_uses_escrow_fee_override() {
 
     // Set escrow fee to 500 bps (5%)
     client.set_escrow_fee(&escrow_id, &500);
+    execute_queued(&env, &client);
 
     let milestones = vec![
         &env,
@@ -1997,6 +2318,8 @@ fn test_refund_expired_uses_escrow_fee_override() {
     let current_time = env.ledger().timestamp();
     let deadline = current_time + 100; // 100 seconds from now
 
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -2024,7 +2347,7 @@ fn test_refund_expired_uses_escrow_fee_override() {
     let expected_refund = 10_000i128 - expected_fee;
 
     assert_eq!(token_client.balance(&depositor), expected_refund);
-    assert_eq!(token_client.balance(&treasury), expected_fee);
+    assert_eq!(client.get_accrued_fees(&token_address), expected_fee);
 }
 
 // #[test]
@@ -2087,7 +2410,8 @@ fn test_zero_fee_valid() {
     let treasury = Address::generate(&env);
     // client.initialize(&treasury, &Some(50));
     // Ensure this matches your contract's expected signature
-    client.initialize(&treasury, &Some(50));
+    init_contract(&env, &client, &treasury, &Some(50));
+    let operator = client.get_role(&Role::Operator);
 
     let depositor = Address::generate(&env);
     let recipient = Address::generate(&env);
@@ -2112,6 +2436,8 @@ fn test_zero_fee_valid() {
         },
     ];
 
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
     client.create_escrow(
         &escrow_id,
         &depositor,
@@ -2129,7 +2455,7 @@ fn test_zero_fee_valid() {
     // With zero fee, recipient gets full amount
     // FIX 2: Ensure we are using i128 for balance comparisons
     assert_eq!(token_client.balance(&recipient), 10_000i128);
-    assert_eq!(token_client.balance(&treasury), 0i128);
+    assert_eq!(client.get_accrued_fees(&token_address), 0i128);
 }
 
 #[test]
@@ -2141,7 +2467,7 @@ fn test_max_fee_10000_bps_valid() {
     let client = VaultixEscrowClient::new(&env, &contract_id);
 
     let treasury = Address::generate(&env);
-    client.initialize(&treasury, &Some(50));
+    init_contract(&env, &client, &treasury, &Some(50));
 
     let admin = Address::generate(&env);
     let (_token_client, _token_admin, token_address) = create_token_contract(&env, &admin);
@@ -2150,3 +2476,658 @@ fn test_max_fee_10000_bps_valid() {
     let result = client.try_set_token_fee(&token_address, &10000);
     assert!(result.is_ok());
 }
+
+#[test]
+fn test_two_step_role_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, VaultixEscrow);
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    client.init(
+        &admin,
+        &operator,
+        &arbitrator,
+        &treasury,
+        &None,
+        &MIN_GOVERNANCE_DELAY,
+    );
+
+    let new_operator = Address::generate(&env);
+    client.propose_role_transfer(&Role::Operator, &new_operator);
+    execute_queued(&env, &client);
+
+    // Nothing changes until the new holder accepts
+    assert_eq!(client.get_role(&Role::Operator), operator);
+    assert_eq!(
+        client.get_pending_role_transfer(&Role::Operator),
+        Some(new_operator.clone())
+    );
+
+    client.accept_role(&Role::Operator);
+    assert_eq!(client.get_role(&Role::Operator), new_operator);
+    assert_eq!(client.get_pending_role_transfer(&Role::Operator), None);
+
+    // RoleUpdated carries the real previous holder
+    let event = env.events().all().last().unwrap();
+    let expected_topics: soroban_sdk::Vec<soroban_sdk::Val> = (
+        Symbol::new(&env, "Vaultix"),
+        Symbol::new(&env, "RoleUpdated"),
+        EVENT_SCHEMA_VERSION,
+        Symbol::new(&env, "Operator"),
+    )
+        .into_val(&env);
+    assert_eq!(event.1, expected_topics);
+    let payload: RoleUpdated = event.2.into_val(&env);
+    assert_eq!(
+        payload,
+        RoleUpdated {
+            previous: operator,
+            holder: new_operator,
+        }
+    );
+
+    let result = client.try_accept_role(&Role::Operator);
+    assert_eq!(result, Err(Ok(Error::NoPendingRoleTransfer)));
+}
+
+#[test]
+fn test_cancel_role_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, VaultixEscrow);
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    client.init(
+        &admin,
+        &operator,
+        &arbitrator,
+        &treasury,
+        &None,
+        &MIN_GOVERNANCE_DELAY,
+    );
+
+    let new_treasury = Address::generate(&env);
+    client.propose_role_transfer(&Role::Treasury, &new_treasury);
+    execute_queued(&env, &client);
+    client.cancel_role_transfer(&Role::Treasury);
+
+    let result = client.try_accept_role(&Role::Treasury);
+    assert_eq!(result, Err(Ok(Error::NoPendingRoleTransfer)));
+    assert_eq!(client.get_role(&Role::Treasury), treasury);
+
+    let result = client.try_cancel_role_transfer(&Role::Arbitrator);
+    assert_eq!(result, Err(Ok(Error::NoPendingRoleTransfer)));
+}
+
+#[test]
+fn test_init_sets_full_config_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, VaultixEscrow);
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+    let treasury = Address::generate(&env);
+
+    let result = client.try_get_full_config();
+    assert_eq!(result, Err(Ok(Error::AdminNotInitialized)));
+
+    client.init(
+        &admin,
+        &operator,
+        &arbitrator,
+        &treasury,
+        &Some(75),
+        &MIN_GOVERNANCE_DELAY,
+    );
+
+    assert_eq!(
+        client.get_full_config(),
+        Config {
+            admin: admin.clone(),
+            operator: operator.clone(),
+            arbitrator: arbitrator.clone(),
+            treasury: treasury.clone(),
+            fee_bps: 75,
+            fee_change_delay: 0,
+            governance_delay: MIN_GOVERNANCE_DELAY,
+            emergency_exit_delay: DEFAULT_EMERGENCY_EXIT_DELAY,
+            retention_period: DEFAULT_RETENTION_PERIOD,
+            credit_mode: false,
+            screening: ScreeningSource::Blocklist,
+        }
+    );
+
+    // The treasury can no longer overwrite configuration by re-initializing
+    let attacker = Address::generate(&env);
+    let result = client.try_init(
+        &attacker,
+        &attacker,
+        &attacker,
+        &attacker,
+        &Some(0),
+        &MIN_GOVERNANCE_DELAY,
+    );
+    assert_eq!(result, Err(Ok(Error::AlreadyInitialized)));
+    assert_eq!(client.get_config(), (treasury, 75));
+}
+
+#[test]
+fn test_token_allowlist_enforces_limits() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = register_contract(&env, None, MIN_GOVERNANCE_DELAY);
+    let operator = client.get_role(&Role::Operator);
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    let token_address = Address::generate(&env);
+    let milestones = fixture_milestones(&env, &[5_000]);
+
+    // Unlisted tokens are rejected before any transfer is attempted
+    let result = client.try_create_escrow(
+        &1u64,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1_706_400_000u64,
+    );
+    assert_eq!(result, Err(Ok(Error::TokenNotAllowed)));
+
+    let result = client.try_allow_token(&operator, &token_address, &2_000, &1_000);
+    assert_eq!(result, Err(Ok(Error::AmountOutOfRange)));
+
+    client.allow_token(&operator, &token_address, &1_000, &4_000);
+    execute_queued(&env, &client);
+    assert_eq!(
+        client.get_allowed_tokens(),
+        vec![&env, token_address.clone()]
+    );
+    assert_eq!(
+        client.get_token_limits(&token_address),
+        Some(TokenLimits {
+            min_amount: 1_000,
+            max_amount: 4_000,
+        })
+    );
+
+    let result = client.try_create_escrow(
+        &1u64,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1_706_400_000u64,
+    );
+    assert_eq!(result, Err(Ok(Error::AmountOutOfRange)));
+
+    // Updating limits keeps a single list entry
+    client.allow_token(&operator, &token_address, &1_000, &5_000);
+    execute_queued(&env, &client);
+    assert_eq!(client.get_allowed_tokens().len(), 1);
+    client.create_escrow(
+        &1u64,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1_706_400_000u64,
+    );
+
+    client.remove_allowed_token(&operator, &token_address);
+    assert_eq!(client.get_allowed_tokens().len(), 0);
+    assert_eq!(client.get_token_limits(&token_address), None);
+
+    // The list is bounded
+    for _ in 0..MAX_ALLOWED_TOKENS {
+        client.allow_token(&operator, &Address::generate(&env), &1, &5_000);
+    }
+    execute_queued(&env, &client);
+    client.allow_token(&operator, &token_address, &1, &5_000);
+    env.ledger()
+        .with_mut(|li| li.timestamp += MIN_GOVERNANCE_DELAY);
+    let change_id = client.get_queued_changes().get(0).unwrap();
+    let result = client.try_execute_queued_change(&change_id);
+    assert_eq!(result, Err(Ok(Error::VectorTooLarge)));
+}
+
+#[test]
+fn test_sweep_excess_leaves_liabilities_and_fees() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, VaultixEscrow);
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let treasury = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let rescuer = Address::generate(&env);
+    init_contract(&env, &client, &treasury, &Some(100));
+    let operator = client.get_role(&Role::Operator);
+
+    let (token_client, token_admin, token_address) = create_token_contract(&env, &admin);
+    token_admin.mint(&depositor, &10_000);
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 4_000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Phase1"),
+        },
+        Milestone {
+            amount: 6_000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Phase2"),
+        },
+    ];
+    client.create_escrow(
+        &1u64,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1_706_400_000u64,
+    );
+    token_client.approve(&depositor, &contract_id, &10_000, &200);
+    client.deposit_funds(&1u64);
+    assert_eq!(client.get_liabilities(&token_address), 10_000);
+
+    // Release 4_000: 3_960 paid out and 40 accrued as fees
+    client.release_milestone(&1u64, &0);
+    assert_eq!(client.get_liabilities(&token_address), 6_000);
+    assert_eq!(client.get_accrued_fees(&token_address), 40);
+
+    // Tokens sent directly to the contract are the only sweepable balance
+    token_admin.mint(&contract_id, &500);
+    client.sweep_excess(&token_address, &rescuer);
+    assert_eq!(token_client.balance(&rescuer), 0);
+    execute_queued(&env, &client);
+    assert_eq!(token_client.balance(&rescuer), 500);
+    client.sweep_excess(&token_address, &rescuer);
+    execute_queued(&env, &client);
+    assert_eq!(token_client.balance(&rescuer), 500);
+    assert_eq!(token_client.balance(&contract_id), 6_040);
+}
+
+#[test]
+fn test_role_registry_grants_multiple_operators() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, VaultixEscrow);
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    client.init(
+        &admin,
+        &operator,
+        &arbitrator,
+        &treasury,
+        &None,
+        &MIN_GOVERNANCE_DELAY,
+    );
+
+    let second_operator = Address::generate(&env);
+    let third_operator = Address::generate(&env);
+    assert!(client.has_role(&Role::Operator, &operator));
+    assert!(!client.has_role(&Role::Operator, &second_operator));
+
+    // Only holders of the role's admin role can grant it
+    let result = client.try_grant_role(&operator, &Role::Operator, &second_operator);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    let result = client.try_grant_role(&admin, &Role::Admin, &second_operator);
+    assert_eq!(result, Err(Ok(Error::RoleNotDelegable)));
+
+    client.grant_role(&admin, &Role::Operator, &second_operator);
+    execute_queued(&env, &client);
+    assert!(client.has_role(&Role::Operator, &second_operator));
+    client.set_paused(&second_operator, &true);
+    client.set_paused(&operator, &false);
+    execute_queued(&env, &client);
+
+    // Operators can manage their own role once it administers itself
+    client.set_role_admin(&Role::Operator, &Role::Operator);
+    execute_queued(&env, &client);
+    assert_eq!(client.get_role_admin(&Role::Operator), Role::Operator);
+    client.grant_role(&second_operator, &Role::Operator, &third_operator);
+    execute_queued(&env, &client);
+
+    client.revoke_role(&operator, &Role::Operator, &second_operator);
+    assert!(!client.has_role(&Role::Operator, &second_operator));
+    let result = client.try_set_paused(&second_operator, &true);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    assert!(client.has_role(&Role::Operator, &third_operator));
+}
+
+#[test]
+fn test_bump_escrow() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, VaultixEscrow);
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let escrow_id = 1u64;
+
+    let (_token_client, _token_admin, token_address) = create_token_contract(&env, &admin);
+    init_with_token(&env, &client, &token_address);
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 1000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Work"),
+        },
+    ];
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+
+    // Anyone can keep an existing escrow alive; unknown escrows are rejected
+    assert_eq!(client.bump_escrow(&escrow_id, &0), None);
+    let result = client.try_bump_escrow(&2u64, &0);
+    assert_eq!(result, Err(Ok(Error::EscrowNotFound)));
+}
+
+#[test]
+fn test_add_milestones_up_to_the_limit() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token_address) = setup(&env, Some(0));
+    let depositor = Address::generate(&env);
+    let chunk = fixture_milestones(&env, &[10; MAX_MILESTONES_PER_CALL as usize]);
+    client.create_escrow(
+        &1u64,
+        &depositor,
+        &Address::generate(&env),
+        &token_address,
+        &chunk,
+        &1_706_400_000u64,
+    );
+
+    let events_before = env.events().all().len();
+    client.add_milestones(&1u64, &fixture_milestones(&env, &[10]));
+    assert!(env.events().all().len() > events_before);
+    let payload: MilestonesAdded = env.events().all().last().unwrap().2.into_val(&env);
+    assert_eq!(
+        payload,
+        MilestonesAdded {
+            count: 1,
+            total_amount: 10 * (MAX_MILESTONES_PER_CALL as i128 + 1),
+        }
+    );
+
+    // A call writes at most one chunk, and the escrow at most `MAX_MILESTONES`
+    let mut too_large = chunk.clone();
+    too_large.push_back(chunk.get(0).unwrap());
+    let result = client.try_add_milestones(&1u64, &too_large);
+    assert_eq!(result, Err(Ok(Error::VectorTooLarge)));
+    while client.get_escrow(&1u64).milestone_count + MAX_MILESTONES_PER_CALL <= MAX_MILESTONES {
+        client.add_milestones(&1u64, &chunk);
+    }
+    let remaining = MAX_MILESTONES - client.get_escrow(&1u64).milestone_count;
+    let last = fixture_milestones(&env, &[10; MAX_MILESTONES as usize][..remaining as usize]);
+    client.add_milestones(&1u64, &last);
+    let escrow = client.get_escrow(&1u64);
+    assert_eq!(escrow.milestone_count, MAX_MILESTONES);
+    assert_eq!(escrow.total_amount, 10 * MAX_MILESTONES as i128);
+    let result = client.try_add_milestones(&1u64, &fixture_milestones(&env, &[10]));
+    assert_eq!(result, Err(Ok(Error::VectorTooLarge)));
+
+    // Keeping every milestone alive takes one page per call
+    let mut pages = 1;
+    let mut next = client.bump_escrow(&1u64, &0);
+    while let Some(start) = next {
+        next = client.bump_escrow(&1u64, &start);
+        pages += 1;
+    }
+    assert_eq!(pages, MAX_MILESTONES.div_ceil(MAX_MILESTONES_PER_CALL));
+
+    // Funded escrows are fixed
+    token::StellarAssetClient::new(&env, &token_address).mint(&depositor, &escrow.total_amount);
+    token::Client::new(&env, &token_address).approve(
+        &depositor,
+        &client.address,
+        &escrow.total_amount,
+        &200,
+    );
+    client.deposit_funds(&1u64);
+    let result = client.try_add_milestones(&1u64, &fixture_milestones(&env, &[10]));
+    assert_eq!(result, Err(Ok(Error::EscrowAlreadyFunded)));
+}
+
+#[test]
+fn test_release_milestones_in_batch() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, VaultixEscrow);
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let escrow_id = 21u64;
+
+    // 1% platform fee
+    let treasury = Address::generate(&env);
+    init_contract(&env, &client, &treasury, &Some(100));
+    let operator = client.get_role(&Role::Operator);
+
+    let (token_client, token_admin, token_address) = create_token_contract(&env, &admin);
+    token_admin.mint(&depositor, &10_000);
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            amount: 2000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Phase1"),
+        },
+        Milestone {
+            amount: 3000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Phase2"),
+        },
+        Milestone {
+            amount: 5000,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Phase3"),
+        },
+    ];
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        &token_address,
+        &milestones,
+        &1706400000u64,
+    );
+    token_client.approve(&depositor, &contract_id, &10_000, &200);
+    client.deposit_funds(&escrow_id);
+
+    // Invalid batches are rejected without releasing anything
+    let result = client.try_release_milestones(&escrow_id, &vec![&env, 0u32, 0u32]);
+    assert_eq!(result, Err(Ok(Error::MilestoneAlreadyReleased)));
+    let result = client.try_release_milestones(&escrow_id, &vec![&env, 2u32, 3u32]);
+    assert_eq!(result, Err(Ok(Error::MilestoneNotFound)));
+    assert_eq!(client.get_escrow(&escrow_id).released_count, 0);
+
+    client.release_milestones(&escrow_id, &vec![&env, 2u32, 0u32]);
+
+    // Payouts and fees match two individual releases
+    assert_eq!(token_client.balance(&recipient), 4950 + 1980);
+    assert_eq!(client.get_accrued_fees(&token_address), 50 + 20);
+    let escrow = client.get_escrow(&escrow_id);
+    assert_eq!(escrow.released_count, 2);
+    assert_eq!(escrow.total_released, 7000);
+    assert_eq!(
+        client.get_milestone(&escrow_id, &1).status,
+        MilestoneStatus::Pending
+    );
+
+    let events = env.events().all();
+    let summary = events.last().unwrap();
+    let expected_topics: soroban_sdk::Vec<soroban_sdk::Val> = (
+        Symbol::new(&env, "Vaultix"),
+        Symbol::new(&env, "MilestonesReleased"),
+        EVENT_SCHEMA_VERSION,
+        escrow_id,
+    )
+        .into_val(&env);
+    assert_eq!(summary.1, expected_topics);
+    let payload: MilestonesReleased = summary.2.into_val(&env);
+    assert_eq!(
+        payload,
+        MilestonesReleased {
+            count: 2,
+            total_payout: 4950 + 1980,
+            total_fee: 50 + 20,
+        }
+    );
+
+    let result = client.try_release_milestones(&escrow_id, &vec![&env, 1u32, 2u32]);
+    assert_eq!(result, Err(Ok(Error::MilestoneAlreadyReleased)));
+}
+
+#[test]
+fn test_create_escrows_in_batch() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, VaultixEscrow);
+    let client = VaultixEscrowClient::new(&env, &contract_id);
+
+    let depositor = Address::generate(&env);
+    let seller_a = Address::generate(&env);
+    let seller_b = Address::generate(&env);
+    let admin = Address::generate(&env);
+
+    let treasury = Address::generate(&env);
+    init_contract(&env, &client, &treasury, &None);
+    let operator = client.get_role(&Role::Operator);
+
+    let (token_client, token_admin, token_address) = create_token_contract(&env, &admin);
+    token_admin.mint(&depositor, &10_000);
+    client.allow_token(&operator, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
+
+    let spec = |escrow_id: u64, recipient: &Address, amount: i128| EscrowSpec {
+        escrow_id,
+        recipient: recipient.clone(),
+        token_address: token_address.clone(),
+        milestones: vec![
+            &env,
+            Milestone {
+                amount,
+                status: MilestoneStatus::Pending,
+                description: symbol_short!("Order"),
+            },
+        ],
+        deadline: 1706400000u64,
+    };
+
+    // A duplicate ID fails the whole batch, leaving the first escrow uncreated
+    let result = client.try_create_escrows(
+        &depositor,
+        &vec![&env, spec(1, &seller_a, 1000), spec(1, &seller_b, 2000)],
+        &false,
+    );
+    assert_eq!(result, Err(Ok(Error::EscrowAlreadyExists)));
+    assert_eq!(client.try_get_state(&1), Err(Ok(Error::EscrowNotFound)));
+
+    // Funding pulls the combined amount in one transfer
+    token_client.approve(&depositor, &contract_id, &3000, &200);
+    let escrow_ids = client.create_escrows(
+        &depositor,
+        &vec![&env, spec(1, &seller_a, 1000), spec(2, &seller_b, 2000)],
+        &true,
+    );
+    assert_eq!(escrow_ids, vec![&env, 1u64, 2u64]);
+    assert_eq!(token_client.balance(&depositor), 7000);
+    assert_eq!(token_client.balance(&contract_id), 3000);
+    assert_eq!(token_client.allowance(&depositor, &contract_id), 0);
+    assert_eq!(client.get_liabilities(&token_address), 3000);
+
+    let escrow = client.get_escrow(&2);
+    assert_eq!(escrow.recipient, seller_b);
+    assert_eq!(escrow.total_amount, 2000);
+    assert_eq!(escrow.status, EscrowStatus::Active);
+    assert_eq!(client.get_status_counts().active, 2);
+    let page = client.get_escrows_by_depositor(&depositor, &0, &10, &None);
+    assert_eq!(page.escrow_ids, escrow_ids);
+
+    // Without enough allowance for the whole batch nothing is created
+    let result = client.try_create_escrows(&depositor, &vec![&env, spec(3, &seller_a, 500)], &true);
+    assert_eq!(result, Err(Ok(Error::TokenTransferFailed)));
+    assert_eq!(client.try_get_state(&3), Err(Ok(Error::EscrowNotFound)));
+}
+
+#[test]
+fn test_create_escrows_up_to_the_batch_cap() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    let (client, token_address) = setup(&env, Some(0));
+    let depositor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token_address).mint(&depositor, &100_000);
+    token::Client::new(&env, &token_address).approve(&depositor, &client.address, &100_000, &200);
+    let spec = |escrow_id: u64| EscrowSpec {
+        escrow_id,
+        recipient: Address::generate(&env),
+        token_address: token_address.clone(),
+        milestones: fixture_milestones(&env, &[600, 400]),
+        deadline: 1_706_400_000u64,
+    };
+
+    let mut specs = Vec::new(&env);
+    for escrow_id in 1..=MAX_BATCH_SIZE as u64 {
+        specs.push_back(spec(escrow_id));
+    }
+    let escrow_ids = client.create_escrows(&depositor, &specs, &true);
+    assert_eq!(escrow_ids.len(), MAX_BATCH_SIZE);
+    assert_eq!(client.get_status_counts().active, MAX_BATCH_SIZE as u64);
+    assert_eq!(
+        client.get_liabilities(&token_address),
+        1_000 * MAX_BATCH_SIZE as i128
+    );
+
+    specs.push_back(spec(MAX_BATCH_SIZE as u64 + 1));
+    let result = client.try_create_escrows(&depositor, &specs, &true);
+    assert_eq!(result, Err(Ok(Error::VectorTooLarge)));
+}
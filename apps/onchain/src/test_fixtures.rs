@@ -0,0 +1,155 @@
+// test_fixtures.rs
+//! Fixtures shared by the test modules.
+use crate::{
+    Milestone, MilestoneStatus, Role, VaultixEscrow, VaultixEscrowClient, MIN_GOVERNANCE_DELAY,
+};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    token, Address, Env, Vec,
+};
+
+/// Helper function to create and initialize a test token
+/// Returns admin client for minting and the token address
+pub(crate) fn create_test_token<'a>(
+    env: &Env,
+    admin: &Address,
+) -> (token::StellarAssetClient<'a>, Address) {
+    let token_address = env.register_stellar_asset_contract(admin.clone());
+    let token_admin_client = token::StellarAssetClient::new(env, &token_address);
+    (token_admin_client, token_address)
+}
+
+/// Helper function to create token client + admin + address
+pub(crate) fn create_token_contract<'a>(
+    env: &Env,
+    admin: &Address,
+) -> (token::Client<'a>, token::StellarAssetClient<'a>, Address) {
+    let (token_admin, token_address) = create_test_token(env, admin);
+    let token_client = token::Client::new(env, &token_address);
+    (token_client, token_admin, token_address)
+}
+
+/// Helper function to initialize the contract with the given treasury and fee,
+/// using freshly generated admin, operator and arbitrator addresses
+pub(crate) fn init_contract(
+    env: &Env,
+    client: &VaultixEscrowClient,
+    treasury: &Address,
+    fee_bps: &Option<i128>,
+) {
+    init_with_delay(env, client, treasury, fee_bps, MIN_GOVERNANCE_DELAY);
+}
+
+fn init_with_delay(
+    env: &Env,
+    client: &VaultixEscrowClient,
+    treasury: &Address,
+    fee_bps: &Option<i128>,
+    governance_delay: u64,
+) {
+    let admin = Address::generate(env);
+    let operator = Address::generate(env);
+    let arbitrator = Address::generate(env);
+    client.init(
+        &admin,
+        &operator,
+        &arbitrator,
+        treasury,
+        fee_bps,
+        &governance_delay,
+    );
+}
+
+/// Helper function to register the escrow contract and initialize it with
+/// `fee_bps` and `governance_delay`, using freshly generated role holders
+pub(crate) fn register_contract<'a>(
+    env: &Env,
+    fee_bps: Option<i128>,
+    governance_delay: u64,
+) -> VaultixEscrowClient<'a> {
+    let contract_id = env.register_contract(None, VaultixEscrow);
+    let client = VaultixEscrowClient::new(env, &contract_id);
+    init_with_delay(
+        env,
+        &client,
+        &Address::generate(env),
+        &fee_bps,
+        governance_delay,
+    );
+    client
+}
+
+/// Helper function to initialize the contract without fees and allowlist a token
+/// with no effective amount limits
+pub(crate) fn init_with_token(env: &Env, client: &VaultixEscrowClient, token_address: &Address) {
+    init_contract(env, client, &Address::generate(env), &Some(0));
+    allow_token(env, client, token_address);
+}
+
+/// Helper function to allowlist a token with no effective amount limits
+fn allow_token(env: &Env, client: &VaultixEscrowClient, token_address: &Address) {
+    let operator = client.get_role(&Role::Operator);
+    client.allow_token(&operator, token_address, &1, &i128::MAX);
+    execute_queued(env, client);
+}
+
+/// Helper function to wait out the governance delay and execute every queued change
+pub(crate) fn execute_queued(env: &Env, client: &VaultixEscrowClient) {
+    let delay = client.get_governance_delay();
+    env.ledger().with_mut(|li| li.timestamp += delay);
+    for change_id in client.get_queued_changes().iter() {
+        client.execute_queued_change(&change_id);
+    }
+}
+
+/// Helper function to register the escrow contract, initialize it with `fee_bps`
+/// (the default platform fee when `None`) and allowlist a fresh token
+pub(crate) fn setup<'a>(env: &Env, fee_bps: Option<i128>) -> (VaultixEscrowClient<'a>, Address) {
+    let client = register_contract(env, fee_bps, MIN_GOVERNANCE_DELAY);
+
+    let (_, token_address) = create_test_token(env, &Address::generate(env));
+    allow_token(env, &client, &token_address);
+
+    (client, token_address)
+}
+
+/// Helper function to build pending milestones of the given amounts
+pub(crate) fn milestones(env: &Env, amounts: &[i128]) -> Vec<Milestone> {
+    let mut milestones = Vec::new(env);
+    for amount in amounts {
+        milestones.push_back(Milestone {
+            amount: *amount,
+            status: MilestoneStatus::Pending,
+            description: symbol_short!("Work"),
+        });
+    }
+    milestones
+}
+
+/// Helper function to create and fund an escrow between two fresh parties with
+/// milestones of the given amounts. Returns the depositor and recipient.
+pub(crate) fn create_funded(
+    env: &Env,
+    client: &VaultixEscrowClient,
+    escrow_id: u64,
+    token_address: &Address,
+    amounts: &[i128],
+) -> (Address, Address) {
+    let depositor = Address::generate(env);
+    let recipient = Address::generate(env);
+    client.create_escrow(
+        &escrow_id,
+        &depositor,
+        &recipient,
+        token_address,
+        &milestones(env, amounts),
+        &1_706_400_000u64,
+    );
+
+    let total: i128 = amounts.iter().sum();
+    token::StellarAssetClient::new(env, token_address).mint(&depositor, &total);
+    token::Client::new(env, token_address).approve(&depositor, &client.address, &total, &200);
+    client.deposit_funds(&escrow_id);
+    (depositor, recipient)
+}
@@ -1,68 +1,416 @@
 use super::*;
-use soroban_sdk::{testutils::Address as _, testutils::Events, Env, Address, Vec, Symbol};
+use crate::test_fixtures::{execute_queued, register_contract};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, BytesN, Env,
+};
 
 #[test]
-fn test_admin_upgrade_and_state_preservation() {
+fn test_upgrade_requires_delay() {
     let env = Env::default();
     env.mock_all_auths();
 
-    // Deploy version A
-    let contract_id = env.register_contract(None, VaultixEscrow);
-    let client = VaultixEscrowClient::new(&env, &contract_id);
+    let client = register_contract(&env, None, MIN_GOVERNANCE_DELAY);
+    let new_wasm_hash = BytesN::from_array(&env, &[7u8; 32]);
 
-    // Set admin
-    let admin = Address::generate(&env);
-    client.init(&admin);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.propose_upgrade(&new_wasm_hash);
+    assert_eq!(client.get_pending_upgrade(), None);
+
+    // The proposal waits out the governance delay before its own timelock starts
+    execute_queued(&env, &client);
+    let proposed_at = 1_000 + MIN_GOVERNANCE_DELAY;
+    let pending = client.get_pending_upgrade().unwrap();
+    assert_eq!(pending.wasm_hash, new_wasm_hash);
+    assert_eq!(pending.eta, proposed_at + UPGRADE_DELAY);
+
+    let result = client.try_execute_upgrade();
+    assert_eq!(result, Err(Ok(Error::TimelockNotReady)));
+
+    client.cancel_upgrade();
+    assert_eq!(client.get_pending_upgrade(), None);
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = proposed_at + UPGRADE_DELAY);
+    let result = client.try_execute_upgrade();
+    assert_eq!(result, Err(Ok(Error::ProposalNotFound)));
+}
+
+/// Version 1 escrow with a single milestone of 1,000 in `status`
+fn legacy_escrow(
+    env: &Env,
+    depositor: &Address,
+    token_address: &Address,
+    status: EscrowStatus,
+) -> EscrowV1 {
+    EscrowV1 {
+        depositor: depositor.clone(),
+        recipient: Address::generate(env),
+        token_address: token_address.clone(),
+        total_amount: 1_000,
+        total_released: 0,
+        milestones: vec![
+            env,
+            Milestone {
+                amount: 1_000,
+                status: MilestoneStatus::Pending,
+                description: symbol_short!("Task"),
+            },
+        ],
+        status,
+        deadline: 1_706_400_000,
+        resolution: Resolution::None,
+    }
+}
 
-    // Create escrow
+#[test]
+fn test_migrate_moves_v1_escrows_in_batches() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = register_contract(&env, None, MIN_GOVERNANCE_DELAY);
+    let contract_id = client.address.clone();
+
+    // Fresh deployments start on the current layout
+    assert_eq!(client.get_version(), CONTRACT_VERSION);
+
+    // Simulate a deployment that predates versioning with two escrows in the v1 layout
     let depositor = Address::generate(&env);
-    let recipient = Address::generate(&env);
-    let token_address = Address::generate(&env);
-    let milestones = Vec::new(&env);
-    milestones.push_back(Milestone {
-        amount: 1000,
-        status: MilestoneStatus::Pending,
-        description: Symbol::new(&env, "Test"),
+    let token_address = env.register_stellar_asset_contract(Address::generate(&env));
+    env.as_contract(&contract_id, || {
+        env.storage().instance().remove(&DataKey::Version);
+        for escrow_id in [1u64, 2u64] {
+            let legacy = legacy_escrow(&env, &depositor, &token_address, EscrowStatus::Active);
+            env.storage()
+                .persistent()
+                .set(&(symbol_short!("escrow"), escrow_id), &legacy);
+        }
     });
-    let deadline = 1706400000u64;
-    client.create_escrow(&1u64, &depositor, &recipient, &token_address, &milestones, &deadline);
+    assert_eq!(client.get_version(), 1);
+
+    // Unknown ids are skipped and the version waits for the remaining escrow
+    let migrated = client.migrate(&2, &vec![&env, 1u64, 3u64], &vec![&env]);
+    assert_eq!(migrated, 1);
+    assert_eq!(client.get_version(), 1);
+    assert_eq!(
+        client.get_migration_progress(),
+        Some(MigrationProgress {
+            escrow_count: 2,
+            migrated: 1,
+        })
+    );
 
-    // Simulate upgrade: deploy version B (same contract, but would add a helper in real scenario)
-    let new_wasm_hash = vec![&env, 1u8, 2u8, 3u8]; // Dummy hash for test
-    let result = client.try_upgrade(&new_wasm_hash);
-    assert!(result.is_ok());
+    // Funds of unmigrated escrows are not yet liabilities, so nothing may move
+    let result = client.try_sweep_excess(&token_address, &depositor);
+    assert_eq!(result, Err(Ok(Error::ContractPaused)));
+    let result = client.try_cancel_escrow(&1);
+    assert_eq!(result, Err(Ok(Error::ContractPaused)));
 
-    // State should be preserved
-    let escrow = client.get_escrow(&1u64);
+    // Already migrated ids are skipped
+    let migrated = client.migrate(&2, &vec![&env, 1u64, 2u64], &vec![&env]);
+    assert_eq!(migrated, 1);
+    assert_eq!(client.get_version(), CONTRACT_VERSION);
+    assert_eq!(client.get_migration_progress(), None);
+
+    let escrow = client.get_escrow(&1);
     assert_eq!(escrow.depositor, depositor);
-    assert_eq!(escrow.recipient, recipient);
-    assert_eq!(escrow.total_amount, 1000);
-
-    // Event should be emitted
-    let events = env.events().all();
-    let found = events.iter().any(|e| {
-        let topics: Vec<_> = e.1.into_val(&env);
-        topics.contains(&Symbol::new(&env, "ContractUpgraded").into_val(&env))
+    assert_eq!(escrow.total_amount, 1_000);
+    assert_eq!(escrow.status, EscrowStatus::Active);
+    assert_eq!(escrow.fee_bps, DEFAULT_FEE_BPS);
+    assert_eq!(escrow.fee_payer, FeePayer::Recipient);
+    assert_eq!(escrow.milestone_count, 1);
+    assert_eq!(client.get_milestone(&1, &0).amount, 1_000);
+    let page = client.get_escrows_by_depositor(&depositor, &0, &10, &None);
+    assert_eq!(page.escrow_ids, vec![&env, 1u64, 2u64]);
+    assert_eq!(client.get_status_counts().active, 2);
+    assert_eq!(client.get_liabilities(&token_address), 2_000);
+
+    env.as_contract(&contract_id, || {
+        assert!(!env
+            .storage()
+            .persistent()
+            .has(&(symbol_short!("escrow"), 1u64)));
+    });
+
+    let result = client.try_migrate(&0, &vec![&env], &vec![&env]);
+    assert_eq!(result, Err(Ok(Error::MigrationAlreadyApplied)));
+}
+
+#[test]
+fn test_migrate_moves_escrows_with_many_milestones() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = register_contract(&env, None, MIN_GOVERNANCE_DELAY);
+    let contract_id = client.address.clone();
+
+    // Version 1 allowed up to 20 milestones per escrow
+    let depositor = Address::generate(&env);
+    let token_address = env.register_stellar_asset_contract(Address::generate(&env));
+    env.as_contract(&contract_id, || {
+        env.storage().instance().remove(&DataKey::Version);
+        let mut large = legacy_escrow(&env, &depositor, &token_address, EscrowStatus::Active);
+        for index in 1..20u32 {
+            large.milestones.push_back(Milestone {
+                amount: 100 + index as i128,
+                status: if index < 5 {
+                    MilestoneStatus::Released
+                } else {
+                    MilestoneStatus::Pending
+                },
+                description: symbol_short!("Task"),
+            });
+        }
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("escrow"), 1u64), &large);
+        for escrow_id in [2u64, 3u64] {
+            let legacy = legacy_escrow(&env, &depositor, &token_address, EscrowStatus::Active);
+            env.storage()
+                .persistent()
+                .set(&(symbol_short!("escrow"), escrow_id), &legacy);
+        }
+    });
+
+    // Batches are capped at a fixed number of escrows
+    let mut ids = Vec::new(&env);
+    for escrow_id in 0..=MAX_BATCH_SIZE as u64 {
+        ids.push_back(escrow_id);
+    }
+    let result = client.try_migrate(&3, &ids, &vec![&env]);
+    assert_eq!(result, Err(Ok(Error::VectorTooLarge)));
+
+    let ids = vec![&env, 1u64, 2u64, 3u64];
+    assert_eq!(client.migrate(&3, &ids, &vec![&env]), 3);
+    assert_eq!(client.get_version(), CONTRACT_VERSION);
+
+    let escrow = client.get_escrow(&1);
+    assert_eq!(escrow.milestone_count, 20);
+    assert_eq!(escrow.released_count, 4);
+    assert_eq!(client.get_milestone(&1, &0).amount, 1_000);
+    assert_eq!(client.get_milestone(&1, &19).amount, 119);
+    assert_eq!(client.get_escrow(&3).milestone_count, 1);
+    assert_eq!(client.get_liabilities(&token_address), 3_000);
+}
+
+#[test]
+fn test_undercounted_migration_leaves_escrows_reachable() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = register_contract(&env, None, MIN_GOVERNANCE_DELAY);
+    let contract_id = client.address.clone();
+
+    let depositor = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token_address = env.register_stellar_asset_contract(token_admin);
+    token::StellarAssetClient::new(&env, &token_address).mint(&contract_id, &2_500);
+    env.as_contract(&contract_id, || {
+        env.storage().instance().remove(&DataKey::Version);
+        for escrow_id in [1u64, 2u64] {
+            let legacy = legacy_escrow(&env, &depositor, &token_address, EscrowStatus::Active);
+            env.storage()
+                .persistent()
+                .set(&(symbol_short!("escrow"), escrow_id), &legacy);
+        }
     });
-    assert!(found);
+
+    // A count of zero bumps the version without moving either escrow
+    assert_eq!(client.migrate(&0, &vec![&env], &vec![&env]), 0);
+    assert_eq!(client.get_version(), CONTRACT_VERSION);
+    assert_eq!(client.get_liabilities(&token_address), 0);
+
+    // Each escrow moves over the first time it is used
+    assert_eq!(client.get_escrow(&1).total_amount, 1_000);
+    assert_eq!(client.bump_escrow(&2, &0), None);
+    assert_eq!(client.get_liabilities(&token_address), 2_000);
+    assert_eq!(client.get_status_counts().active, 2);
+    let page = client.get_escrows_by_depositor(&depositor, &0, &10, &None);
+    assert_eq!(page.escrow_ids, vec![&env, 1u64, 2u64]);
+
+    // Only the stray balance can be swept
+    let rescuer = Address::generate(&env);
+    client.sweep_excess(&token_address, &rescuer);
+    execute_queued(&env, &client);
+    assert_eq!(
+        token::Client::new(&env, &token_address).balance(&rescuer),
+        500
+    );
+
+    client.cancel_escrow(&1);
+    assert_eq!(client.get_escrow(&1).status, EscrowStatus::Cancelled);
 }
 
 #[test]
-fn test_upgrade_rejects_non_admin() {
+fn test_migrate_consolidates_legacy_config() {
     let env = Env::default();
     env.mock_all_auths();
 
     let contract_id = env.register_contract(None, VaultixEscrow);
     let client = VaultixEscrowClient::new(&env, &contract_id);
 
-    // Set admin
+    // Simulate a version 1 deployment that stored roles and settings under separate keys
+    let admin = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        let persistent = env.storage().persistent();
+        persistent.set(&symbol_short!("admin"), &admin);
+        persistent.set(&Symbol::new(&env, "operator"), &operator);
+        persistent.set(&Symbol::new(&env, "arbitrator"), &arbitrator);
+        let instance = env.storage().instance();
+        instance.set(&symbol_short!("treasury"), &treasury);
+        instance.set(&symbol_short!("fee_bps"), &120i128);
+        instance.set(&symbol_short!("state"), &ContractState::Paused);
+    });
+
+    let result = client.try_get_full_config();
+    assert_eq!(result, Err(Ok(Error::AdminNotInitialized)));
+    // The legacy configuration cannot be overwritten by initializing again
+    let result = client.try_init(
+        &operator,
+        &operator,
+        &operator,
+        &operator,
+        &None,
+        &MIN_GOVERNANCE_DELAY,
+    );
+    assert_eq!(result, Err(Ok(Error::AlreadyInitialized)));
+
+    env.ledger().with_mut(|li| li.timestamp = 5_000);
+    client.migrate(&0, &vec![&env], &vec![&env]);
+
+    let config = client.get_full_config();
+    assert_eq!(config.admin, admin);
+    assert_eq!(config.operator, operator);
+    assert_eq!(config.arbitrator, arbitrator);
+    assert_eq!(config.treasury, treasury);
+    assert_eq!(config.fee_bps, 120);
+    assert_eq!(client.get_version(), CONTRACT_VERSION);
+    assert!(client.is_paused(&PausableOperation::Create, &None));
+
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().persistent().has(&symbol_short!("admin")));
+        assert!(!env.storage().instance().has(&symbol_short!("treasury")));
+        assert!(!env.storage().instance().has(&symbol_short!("state")));
+        // The carried-over pause counts towards the emergency exit from migration on
+        let paused_at: Option<u64> = env.storage().instance().get(&DataKey::PausedAt);
+        assert_eq!(paused_at, Some(5_000));
+    });
+}
+
+#[test]
+fn test_init_refuses_partial_legacy_state() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, VaultixEscrow);
+    let client = VaultixEscrowClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
-    client.init(&admin);
-
-    // Try upgrade as non-admin
-    let new_wasm_hash = vec![&env, 1u8, 2u8, 3u8];
-    let not_admin = Address::generate(&env);
-    env.set_source_account(&not_admin);
-    let result = client.try_upgrade(&new_wasm_hash);
-    assert_eq!(result, Err(Ok(Error::UnauthorizedAccess)));
+    let treasury = Address::generate(&env);
+
+    // Version 1 set the treasury separately from the roles, so either may be missing.
+    // Only the presence of each key matters here.
+    for legacy_key in [
+        symbol_short!("treasury"),
+        symbol_short!("fee_bps"),
+        symbol_short!("state"),
+    ] {
+        env.as_contract(&contract_id, || {
+            env.storage().instance().set(&legacy_key, &treasury);
+        });
+        let result = client.try_init(
+            &admin,
+            &admin,
+            &admin,
+            &treasury,
+            &None,
+            &MIN_GOVERNANCE_DELAY,
+        );
+        assert_eq!(result, Err(Ok(Error::AlreadyInitialized)));
+        env.as_contract(&contract_id, || {
+            env.storage().instance().remove(&legacy_key);
+        });
+    }
+
+    client.init(
+        &admin,
+        &admin,
+        &admin,
+        &treasury,
+        &None,
+        &MIN_GOVERNANCE_DELAY,
+    );
+
+    // Ids of version 1 escrows stay taken until they are migrated
+    let depositor = Address::generate(&env);
+    let token_address = env.register_stellar_asset_contract(Address::generate(&env));
+    env.as_contract(&contract_id, || {
+        let legacy = legacy_escrow(&env, &depositor, &token_address, EscrowStatus::Active);
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("escrow"), 7u64), &legacy);
+    });
+    client.allow_token(&admin, &token_address, &1, &i128::MAX);
+    execute_queued(&env, &client);
+    let result = client.try_create_escrow(
+        &7,
+        &depositor,
+        &Address::generate(&env),
+        &token_address,
+        &vec![
+            &env,
+            Milestone {
+                amount: 1_000,
+                status: MilestoneStatus::Pending,
+                description: symbol_short!("Task"),
+            },
+        ],
+        &1_706_400_000,
+    );
+    assert_eq!(result, Err(Ok(Error::EscrowAlreadyExists)));
+}
+
+#[test]
+fn test_migrate_moves_fee_overrides() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = register_contract(&env, None, MIN_GOVERNANCE_DELAY);
+    let contract_id = client.address.clone();
+
+    let depositor = Address::generate(&env);
+    let token_address = Address::generate(&env);
+    let other_token = Address::generate(&env);
+    env.as_contract(&contract_id, || {
+        env.storage().instance().remove(&DataKey::Version);
+        let persistent = env.storage().persistent();
+        let legacy = legacy_escrow(&env, &depositor, &token_address, EscrowStatus::Created);
+        persistent.set(&(symbol_short!("escrow"), 1u64), &legacy);
+        persistent.set(&(symbol_short!("escfee"), 1u64), &25i128);
+        persistent.set(&(symbol_short!("tokfee"), token_address.clone()), &40i128);
+        persistent.set(&(symbol_short!("tokfee"), other_token.clone()), &60i128);
+    });
+
+    client.migrate(&1, &vec![&env, 1u64], &vec![&env, other_token.clone()]);
+    assert_eq!(client.get_version(), CONTRACT_VERSION);
+
+    // The escrow override wins over its token's when snapshotting the fee
+    assert_eq!(client.get_escrow(&1).fee_bps, 25);
+
+    env.as_contract(&contract_id, || {
+        let persistent = env.storage().persistent();
+        assert_eq!(persistent.get(&DataKey::EscrowFee(1)), Some(25i128));
+        assert_eq!(
+            persistent.get(&DataKey::TokenFee(token_address.clone())),
+            Some(40i128)
+        );
+        assert_eq!(
+            persistent.get(&DataKey::TokenFee(other_token.clone())),
+            Some(60i128)
+        );
+        assert!(!persistent.has(&(symbol_short!("escfee"), 1u64)));
+        assert!(!persistent.has(&(symbol_short!("tokfee"), other_token.clone())));
+    });
 }